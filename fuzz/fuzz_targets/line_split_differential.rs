@@ -0,0 +1,31 @@
+#![no_main]
+//! Differential target: writes the same random byte buffer to a temp file
+//! and asserts that the mmap and buffered-reader line-splitting backends
+//! agree byte-for-byte (after CRLF/UTF-8 normalization, exactly as
+//! `for_each_line` performs it). This locks down the invariant that
+//! `iter_lines_auto` behaves identically regardless of which backend the
+//! mmap threshold selects.
+use libfuzzer_sys::fuzz_target;
+use tattletale::io::{iter_lines_bufread, iter_lines_mmap};
+
+fuzz_target!(|data: &[u8]| {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fuzz_input.txt");
+    if std::fs::write(&path, data).is_err() {
+        return;
+    }
+
+    let mmap_lines: Vec<String> = match iter_lines_mmap(&path) {
+        Ok(iter) => iter.flatten().collect(),
+        Err(_) => return,
+    };
+    let bufread_lines: Vec<String> = match iter_lines_bufread(&path) {
+        Ok(iter) => iter.flatten().collect(),
+        Err(_) => return,
+    };
+
+    assert_eq!(
+        mmap_lines, bufread_lines,
+        "mmap and bufread line splitters disagree on the same byte buffer"
+    );
+});
@@ -0,0 +1,45 @@
+#![no_main]
+//! No-panic target: feeds arbitrary (often malformed) bytes through the
+//! potfile and NTDS line parsers. The parsers are expected to return `Err`
+//! on malformed input, never panic — that invariant is what silently dropped
+//! malformed lines otherwise hides.
+use libfuzzer_sys::fuzz_target;
+use tattletale::{dit::parse_dit_line, pot::parse_pot_line};
+
+/// A fuzz-generated line, built either from well-formed-looking fields or
+/// deliberately adversarial bytes (stray colons, embedded backslashes,
+/// truncated hashes, non-UTF-8, oversized fields, CRLF vs LF).
+#[derive(Debug, arbitrary::Arbitrary)]
+struct FuzzLine {
+    field_count: u8,
+    raw_fields: Vec<Vec<u8>>,
+    use_backslash_separator: bool,
+    trailing_crlf: bool,
+}
+
+impl FuzzLine {
+    fn render(&self) -> Vec<u8> {
+        let n = (self.field_count % 6) as usize;
+        let mut out = Vec::new();
+        for (i, field) in self.raw_fields.iter().take(n).enumerate() {
+            if i > 0 {
+                out.push(b':');
+            }
+            if i == 0 && self.use_backslash_separator {
+                out.push(b'\\');
+            }
+            out.extend_from_slice(field);
+        }
+        if self.trailing_crlf {
+            out.extend_from_slice(b"\r\n");
+        }
+        out
+    }
+}
+
+fuzz_target!(|line: FuzzLine| {
+    let bytes = line.render();
+    let text = String::from_utf8_lossy(&bytes);
+    let _ = parse_pot_line(&text);
+    let _ = parse_dit_line(&text);
+});
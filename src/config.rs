@@ -0,0 +1,129 @@
+//! Layered configuration for target marking and password policy, so
+//! operators can tune an engagement without recompiling.
+//!
+//! Precedence is CLI overrides > TOML file > built-in [`Config::default`].
+//! Load a file with [`Config::from_file`], then apply [`Config::with_cli_overrides`]
+//! with whatever the CLI actually supplied (an override field left as `None`
+//! falls through to the file/default value unchanged).
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Errors returned while loading a config file.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read config file {0}: {1}")]
+    Read(String, std::io::Error),
+    #[error("failed to parse config file {0}: {1}")]
+    Parse(String, toml::de::Error),
+}
+
+/// Tunable target-marking and password-policy settings. Deserialized from
+/// TOML; any field omitted from the file keeps its [`Config::default`] value.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Case-insensitive regex patterns matched against `sam_account_name`
+    /// and `down_level_logon_name` to auto-flag high-value accounts, in
+    /// addition to an explicit target list or group-membership expansion.
+    pub target_patterns: Vec<String>,
+    /// Group names (matched case-insensitively) whose members are flagged
+    /// as targets by `Engine::mark_sensitive_group_targets`, overriding the
+    /// built-in `groups::SENSITIVE_GROUPS` list.
+    pub admin_groups: Vec<String>,
+    /// Cracked passwords shorter than this are flagged via
+    /// `Credential::is_weak_password`.
+    pub min_password_length: usize,
+    /// Minimum confidence (0.0-1.0) a crack match must meet to be accepted.
+    /// The current potfile matcher is an exact hash lookup (confidence
+    /// 1.0), so this threshold has no effect yet; it exists for a future
+    /// fuzzy/rule-based crack matcher.
+    pub crack_confidence_threshold: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            target_patterns: vec!["(?i)admin".to_string(), "(?i)backup".to_string()],
+            admin_groups: crate::groups::SENSITIVE_GROUPS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            min_password_length: 8,
+            crack_confidence_threshold: 1.0,
+        }
+    }
+}
+
+impl Config {
+    /// Load and parse a TOML config file. Missing fields fall back to
+    /// [`Config::default`] via `#[serde(default)]`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| ConfigError::Read(path.as_ref().display().to_string(), e))?;
+        toml::from_str(&text).map_err(|e| ConfigError::Parse(path.as_ref().display().to_string(), e))
+    }
+
+    /// Apply CLI-supplied overrides on top of `self` (already either
+    /// defaults or file-loaded). A `None` override leaves the existing
+    /// value untouched, so CLI flags the operator didn't pass fall through
+    /// to the file/default value.
+    pub fn with_cli_overrides(mut self, overrides: CliOverrides) -> Config {
+        if let Some(v) = overrides.target_patterns {
+            self.target_patterns = v;
+        }
+        if let Some(v) = overrides.admin_groups {
+            self.admin_groups = v;
+        }
+        if let Some(v) = overrides.min_password_length {
+            self.min_password_length = v;
+        }
+        if let Some(v) = overrides.crack_confidence_threshold {
+            self.crack_confidence_threshold = v;
+        }
+        self
+    }
+}
+
+/// CLI-supplied config overrides. Every field is optional so the CLI layer
+/// only needs to populate the flags the operator actually passed.
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    pub target_patterns: Option<Vec<String>>,
+    pub admin_groups: Option<Vec<String>>,
+    pub min_password_length: Option<usize>,
+    pub crack_confidence_threshold: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_has_builtin_admin_groups_and_password_length() {
+        let cfg = Config::default();
+        assert_eq!(cfg.min_password_length, 8);
+        assert!(cfg.admin_groups.iter().any(|g| g == "wheel"));
+    }
+
+    #[test]
+    fn from_file_parses_partial_toml_and_keeps_other_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tattletale.toml");
+        std::fs::write(&path, "min_password_length = 12\n").unwrap();
+        let cfg = Config::from_file(&path).unwrap();
+        assert_eq!(cfg.min_password_length, 12);
+        assert_eq!(cfg.crack_confidence_threshold, 1.0);
+    }
+
+    #[test]
+    fn cli_overrides_take_precedence_over_file_and_defaults() {
+        let cfg = Config::default().with_cli_overrides(CliOverrides {
+            min_password_length: Some(16),
+            ..Default::default()
+        });
+        assert_eq!(cfg.min_password_length, 16);
+        // untouched fields keep their default
+        assert_eq!(cfg.crack_confidence_threshold, 1.0);
+    }
+}
@@ -7,7 +7,9 @@
 //!
 //! Use [`Credential::fill_from_dit`] to populate a credential from a DIT line
 //! and [`Credential::crack`] to set the cleartext when present in potfiles.
-#[derive(Debug, Clone, Eq)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Eq, Serialize)]
 /// Represents a parsed account entry with associated hash metadata and state.
 pub struct Credential {
     pub down_level_logon_name: String,
@@ -23,11 +25,96 @@ pub struct Credential {
     pub nt_hashtext: String,
     pub is_target: bool,
     pub target_filenames: Vec<String>,
-    pub is_hash_type_lm: bool,
-    pub is_hash_type_nt: bool,
-    pub is_hash_type_both: bool,
+    /// Sensitive groups (see `groups::SENSITIVE_GROUPS`) that caused
+    /// `is_target` to be set via group-membership expansion, as opposed to an
+    /// exact match against a flat target list.
+    pub target_reasons: Vec<String>,
+    pub hash_type: HashType,
     pub is_hash_null: bool,
     pub is_cracked: bool,
+    /// Set by `Engine::apply_config_policies` when this credential is
+    /// cracked and its cleartext is shorter than `Config::min_password_length`.
+    pub is_weak_password: bool,
+}
+
+/// Hash algorithm/format a `Credential`'s `hashtext` was classified as, so
+/// reports and exporters can group or segment by algorithm. Potfile crack
+/// matching in `Engine` keys on the full `hashtext` regardless of this
+/// classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashType {
+    #[default]
+    Unknown,
+    /// Windows LM hash only (NT hash field was null).
+    Lm,
+    /// Windows NT hash only (LM hash field was null).
+    Nt,
+    /// Both LM and NT hashes present for the same account.
+    LmAndNt,
+    Md5Crypt,
+    Bcrypt,
+    Sha256Crypt,
+    Sha512Crypt,
+    Yescrypt,
+    LdapSha,
+    LdapSsha,
+}
+
+impl std::fmt::Display for HashType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            HashType::Unknown => "unknown",
+            HashType::Lm => "lm",
+            HashType::Nt => "nt",
+            HashType::LmAndNt => "lm_and_nt",
+            HashType::Md5Crypt => "md5crypt",
+            HashType::Bcrypt => "bcrypt",
+            HashType::Sha256Crypt => "sha256crypt",
+            HashType::Sha512Crypt => "sha512crypt",
+            HashType::Yescrypt => "yescrypt",
+            HashType::LdapSha => "ldap_sha",
+            HashType::LdapSsha => "ldap_ssha",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Classify a hash string by its format. A fixed-length 32-char hex string is
+/// a Windows LM/NT hash (the two are indistinguishable by shape alone, so the
+/// well-known null-hash constants are compared only to recognize a genuinely
+/// absent hash; `Credential::fill_from_dit` disambiguates LM vs NT itself
+/// using the DIT line's separate LM/NT fields). Unix crypt()/LDAP hashes are
+/// recognized by their `$id$`/`{...}` prefix.
+pub fn detect_hash_type(hash: &str) -> HashType {
+    if hash == Credential::NULL_HASH_LM || hash == Credential::NULL_HASH_NT {
+        return HashType::Unknown;
+    }
+    if hash.len() == 32 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return HashType::Nt;
+    }
+    if hash.starts_with("$1$") {
+        return HashType::Md5Crypt;
+    }
+    if hash.starts_with("$2a$") || hash.starts_with("$2b$") || hash.starts_with("$2y$") {
+        return HashType::Bcrypt;
+    }
+    if hash.starts_with("$5$") {
+        return HashType::Sha256Crypt;
+    }
+    if hash.starts_with("$6$") {
+        return HashType::Sha512Crypt;
+    }
+    if hash.starts_with("$y$") {
+        return HashType::Yescrypt;
+    }
+    if hash.starts_with("{SSHA}") {
+        return HashType::LdapSsha;
+    }
+    if hash.starts_with("{SHA}") {
+        return HashType::LdapSha;
+    }
+    HashType::Unknown
 }
 
 impl PartialEq for Credential {
@@ -70,11 +157,11 @@ impl Credential {
             nt_hashtext: String::new(),
             is_target: false,
             target_filenames: Vec::new(),
-            is_hash_type_lm: false,
-            is_hash_type_nt: false,
-            is_hash_type_both: false,
+            target_reasons: Vec::new(),
+            hash_type: HashType::Unknown,
             is_hash_null: false,
             is_cracked: false,
+            is_weak_password: false,
         }
     }
 
@@ -130,18 +217,36 @@ impl Credential {
         if self.lm_hashtext == Self::NULL_HASH_LM && self.nt_hashtext == Self::NULL_HASH_NT {
             self.is_hash_null = true;
         }
-        if self.lm_hashtext != Self::NULL_HASH_LM {
-            self.is_hash_type_lm = true;
+        let lm_present = self.lm_hashtext != Self::NULL_HASH_LM;
+        let nt_present = self.nt_hashtext != Self::NULL_HASH_NT;
+        if lm_present {
             self.is_hash_null = false;
             self.hashtext = self.lm_hashtext.clone();
         }
-        if self.nt_hashtext != Self::NULL_HASH_NT {
-            self.is_hash_type_nt = true;
+        if nt_present {
             self.is_hash_null = false;
             self.hashtext = self.nt_hashtext.clone();
         }
-        if self.is_hash_type_lm && self.is_hash_type_nt {
-            self.is_hash_type_both = true;
+        self.hash_type = match (lm_present, nt_present) {
+            (true, true) => HashType::LmAndNt,
+            (true, false) => HashType::Lm,
+            (false, true) => HashType::Nt,
+            (false, false) => HashType::Unknown,
+        };
+    }
+
+    /// Populate fields derived from a joined `/etc/passwd`+`/etc/shadow`
+    /// entry. Sets identity fields via `fill_with_username`, then treats
+    /// `crypt_hash` as the effective `hashtext` unless it's one of the
+    /// well-known locked/disabled markers (`!`, `!!`, `*`, or empty), in
+    /// which case `is_hash_null` is set instead and no hash is recorded.
+    pub fn fill_from_shadow(&mut self, username: &str, crypt_hash: &str) {
+        self.fill_with_username(username);
+        if matches!(crypt_hash, "" | "!" | "!!" | "*" | "*LK*") {
+            self.is_hash_null = true;
+        } else {
+            self.hashtext = crypt_hash.to_string();
+            self.hash_type = detect_hash_type(crypt_hash);
         }
     }
 
@@ -152,6 +257,13 @@ impl Credential {
             self.is_cracked = true;
         }
     }
+
+    /// Flag `is_weak_password` if this credential is cracked and its
+    /// cleartext is shorter than `min_length`. A no-op for uncracked
+    /// credentials, since no password policy can be evaluated without one.
+    pub fn check_password_policy(&mut self, min_length: usize) {
+        self.is_weak_password = self.is_cracked && self.cleartext.len() < min_length;
+    }
 }
 
 #[cfg(test)]
@@ -185,12 +297,41 @@ mod tests {
             Credential::NULL_HASH_LM,
             "8846f7eaee8fb117ad06bdd830b7586c",
         );
-        assert!(c.is_hash_type_nt);
-        assert!(!c.is_hash_type_lm);
+        assert_eq!(c.hash_type, HashType::Nt);
         assert_eq!(c.hashtext, "8846f7eaee8fb117ad06bdd830b7586c");
         assert!(!c.is_hash_null);
     }
 
+    #[test]
+    fn fill_from_dit_flags_both_lm_and_nt_present() {
+        let mut c = Credential::new();
+        c.fill_from_dit(
+            "DOMAIN\\Carol",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+        );
+        assert_eq!(c.hash_type, HashType::LmAndNt);
+        // NT wins when both are present
+        assert_eq!(c.hashtext, "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    }
+
+    #[test]
+    fn detect_hash_type_recognizes_crypt_and_ldap_prefixes() {
+        assert_eq!(detect_hash_type(Credential::NULL_HASH_LM), HashType::Unknown);
+        assert_eq!(
+            detect_hash_type("8846f7eaee8fb117ad06bdd830b7586c"),
+            HashType::Nt
+        );
+        assert_eq!(detect_hash_type("$1$abc$def"), HashType::Md5Crypt);
+        assert_eq!(detect_hash_type("$2b$10$abc"), HashType::Bcrypt);
+        assert_eq!(detect_hash_type("$5$abc$def"), HashType::Sha256Crypt);
+        assert_eq!(detect_hash_type("$6$abc$def"), HashType::Sha512Crypt);
+        assert_eq!(detect_hash_type("$y$abc$def"), HashType::Yescrypt);
+        assert_eq!(detect_hash_type("{SHA}abc"), HashType::LdapSha);
+        assert_eq!(detect_hash_type("{SSHA}abc"), HashType::LdapSsha);
+        assert_eq!(detect_hash_type("not-a-hash"), HashType::Unknown);
+    }
+
     #[test]
     fn crack_sets_is_cracked_when_nonempty() {
         let mut c = Credential::new();
@@ -199,4 +340,39 @@ mod tests {
         c.crack("Password1!");
         assert!(c.is_cracked);
     }
+
+    #[test]
+    fn fill_from_shadow_sets_hashtext_for_normal_accounts() {
+        let mut c = Credential::new();
+        c.fill_from_shadow("alice", "$6$abc$def123");
+        assert_eq!(c.sam_account_name, "alice");
+        assert_eq!(c.hashtext, "$6$abc$def123");
+        assert_eq!(c.hash_type, HashType::Sha512Crypt);
+        assert!(!c.is_hash_null);
+    }
+
+    #[test]
+    fn fill_from_shadow_flags_locked_accounts_as_null() {
+        let mut c = Credential::new();
+        c.fill_from_shadow("root", "!");
+        assert!(c.is_hash_null);
+        assert!(c.hashtext.is_empty());
+    }
+
+    #[test]
+    fn check_password_policy_flags_short_cracked_passwords_only() {
+        let mut uncracked = Credential::new();
+        uncracked.check_password_policy(8);
+        assert!(!uncracked.is_weak_password);
+
+        let mut weak = Credential::new();
+        weak.crack("abc");
+        weak.check_password_policy(8);
+        assert!(weak.is_weak_password);
+
+        let mut strong = Credential::new();
+        strong.crack("correct horse battery staple");
+        strong.check_password_policy(8);
+        assert!(!strong.is_weak_password);
+    }
 }
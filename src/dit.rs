@@ -1,3 +1,9 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
 use crate::credential::Credential;
 
 #[derive(Debug, thiserror::Error)]
@@ -38,14 +44,69 @@ pub fn parse_dit_contents(contents: &str) -> Vec<Credential> {
             if trimmed.is_empty() {
                 return None;
             }
-            match parse_dit_line(trimmed) {
-                Ok(c) => Some(c),
-                Err(_) => None,
-            }
+            parse_dit_line(trimmed).ok()
         })
         .collect()
 }
 
+/// Memory-map a DIT export file and lazily parse it line-by-line, without
+/// requiring the whole file to be read into a `String` up front. Skips blank
+/// and malformed lines exactly like [`parse_dit_contents`]; each yielded line
+/// is validated as UTF-8 (invalid lines are treated as malformed and skipped,
+/// mirroring the lossless-bulk-path behavior of trimming and `filter_map`).
+pub fn parse_dit_file(path: &Path) -> io::Result<impl Iterator<Item = Credential>> {
+    let file = File::open(path)?;
+    let mmap = unsafe { Mmap::map(&file)? };
+    Ok(DitFileLines { mmap, pos: 0 }.filter_map(|line| {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        parse_dit_line(trimmed).ok()
+    }))
+}
+
+/// Iterator over the raw, newline-delimited `&str` lines of a memory-mapped
+/// DIT file. Kept private: callers go through [`parse_dit_file`], which
+/// already applies the shared blank/malformed-line filtering. A line with
+/// invalid UTF-8 is skipped, not treated as end-of-input: `next()` keeps
+/// advancing through subsequent lines instead of returning `None` early.
+struct DitFileLines {
+    mmap: Mmap,
+    pos: usize,
+}
+
+impl Iterator for DitFileLines {
+    type Item = String;
+    fn next(&mut self) -> Option<Self::Item> {
+        let data: &[u8] = &self.mmap;
+        while self.pos < data.len() {
+            let start = self.pos;
+            let end = match memchr::memchr(b'\n', &data[self.pos..]) {
+                Some(off) => {
+                    let end = self.pos + off;
+                    self.pos = end + 1;
+                    end
+                }
+                None => {
+                    self.pos = data.len();
+                    data.len()
+                }
+            };
+            let slice = if data[start..end].ends_with(b"\r") {
+                &data[start..end - 1]
+            } else {
+                &data[start..end]
+            };
+            match std::str::from_utf8(slice) {
+                Ok(s) => return Some(s.to_string()),
+                Err(_) => continue,
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,8 +118,7 @@ mod tests {
         let c = parse_dit_line(line).unwrap();
         assert_eq!(c.sam_account_name, "User");
         assert_eq!(c.domain, "DOMAIN");
-        assert!(c.is_hash_type_nt);
-        assert!(!c.is_hash_type_lm);
+        assert_eq!(c.hash_type, crate::credential::HashType::Nt);
     }
 
     #[test]
@@ -71,4 +131,35 @@ mod tests {
         assert_eq!(creds[0].sam_account_name, "A");
         assert_eq!(creds[1].sam_account_name, "B");
     }
+
+    #[test]
+    fn parse_dit_file_matches_bulk_parsing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ntds.txt");
+        std::fs::write(
+            &path,
+            "\nINVALID\nDOMAIN\\A:1:x:y:z:extra\nDOMAIN\\B:2::31d6cfe0d16ae931b73c59d7e0c089c0\n",
+        )
+        .unwrap();
+        let creds: Vec<Credential> = parse_dit_file(&path).unwrap().collect();
+        assert_eq!(creds.len(), 2);
+        assert_eq!(creds[0].sam_account_name, "A");
+        assert_eq!(creds[1].sam_account_name, "B");
+    }
+
+    #[test]
+    fn parse_dit_file_skips_invalid_utf8_line_instead_of_truncating() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ntds.txt");
+        let mut bytes = b"DOMAIN\\A:1:aad3b435b51404eeaad3b435b51404ee:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n".to_vec();
+        bytes.extend_from_slice(&[0xff, 0xfe, b'\n']);
+        bytes.extend_from_slice(
+            b"DOMAIN\\B:2:aad3b435b51404eeaad3b435b51404ee:bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n",
+        );
+        std::fs::write(&path, bytes).unwrap();
+        let creds: Vec<Credential> = parse_dit_file(&path).unwrap().collect();
+        assert_eq!(creds.len(), 2);
+        assert_eq!(creds[0].sam_account_name, "A");
+        assert_eq!(creds[1].sam_account_name, "B");
+    }
 }
@@ -14,14 +14,15 @@
 //! # }
 //! ```
 use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
 use crate::dit::parse_dit_line;
-use crate::io::{DEFAULT_MMAP_THRESHOLD_BYTES, iter_lines_auto};
+use crate::io::DEFAULT_MMAP_THRESHOLD_BYTES;
 use crate::{
-    credential::Credential, dit::parse_dit_contents, pot::parse_pot_contents,
+    config::Config, credential::Credential, dit::parse_dit_contents, pot::parse_pot_contents,
     targets::parse_targets,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::Path;
 
 /// Aggregates parsed credentials and exposes loading helpers.
@@ -30,14 +31,94 @@ pub struct Engine {
     pub credentials: Vec<Credential>,
     /// Optional counts collected during parsing
     pub parse_stats: Option<ParseStats>,
+    /// Optional per-phase timings and parse counters, populated by the
+    /// `_with_diagnostics` load variants.
+    pub diagnostics: Option<Diagnostics>,
+    /// Occurrence count per `hashtext`, populated by the intra-file chunked
+    /// parallel loader. A cheap byproduct of the per-segment merge that
+    /// hints at shared-hash groups without a separate pass.
+    pub hash_occurrences: Option<HashMap<String, usize>>,
+    /// Rayon thread pool size override for the `_parallel_*` load paths, set
+    /// via `with_threads`. `None` uses rayon's global pool (all cores).
+    threads: Option<usize>,
+    /// Target-marking and password-policy settings applied by
+    /// `apply_config_policies`, set via `with_config`. Defaults to
+    /// `Config::default()`.
+    pub config: Config,
 }
 
 impl Engine {
-    /// Create an empty engine with no loaded credentials.
+    /// Create an empty engine with no loaded credentials and a default `Config`.
     pub fn new() -> Self {
         Self {
             credentials: Vec::new(),
             parse_stats: None,
+            diagnostics: None,
+            hash_occurrences: None,
+            threads: None,
+            config: Config::default(),
+        }
+    }
+
+    /// Consuming builder: pin the `_parallel_*` load paths to a rayon thread
+    /// pool of exactly `n` threads instead of the global pool, so results are
+    /// reproducible regardless of how many cores the host happens to have.
+    pub fn with_threads(mut self, n: usize) -> Self {
+        self.threads = Some(n);
+        self
+    }
+
+    /// Consuming builder: use `config` (typically `Config::default()` layered
+    /// with `Config::from_file` and `Config::with_cli_overrides`) in place of
+    /// built-in defaults for `apply_config_policies` and
+    /// `mark_sensitive_group_targets`.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Apply `self.config`'s target-pattern regexes and password policy to
+    /// already-loaded credentials: flags any `sam_account_name` or
+    /// `down_level_logon_name` matching a `target_patterns` regex as a
+    /// target (recording `"pattern:<pattern>"` in `target_reasons`), and sets
+    /// `is_weak_password` on cracked credentials below `min_password_length`.
+    /// Call this after loading (and, if used, after `mark_sensitive_group_targets`).
+    pub fn apply_config_policies(&mut self) -> Result<()> {
+        let patterns: Vec<regex::Regex> = self
+            .config
+            .target_patterns
+            .iter()
+            .map(|p| regex::Regex::new(p).with_context(|| format!("invalid target pattern {p}")))
+            .collect::<Result<_>>()?;
+
+        for c in &mut self.credentials {
+            for (pattern, re) in self.config.target_patterns.iter().zip(patterns.iter()) {
+                if re.is_match(&c.sam_account_name) || re.is_match(&c.down_level_logon_name) {
+                    c.is_target = true;
+                    let reason = format!("pattern:{pattern}");
+                    if !c.target_reasons.contains(&reason) {
+                        c.target_reasons.push(reason);
+                    }
+                }
+            }
+            c.check_password_policy(self.config.min_password_length);
+        }
+        Ok(())
+    }
+
+    /// Run `f` on a dedicated `n`-thread rayon pool when `threads` is set,
+    /// otherwise run it directly on whatever pool (global or current) is
+    /// already active.
+    fn run_on_pool<T: Send>(threads: Option<usize>, f: impl FnOnce() -> T + Send) -> Result<T> {
+        match threads {
+            Some(n) => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .context("build rayon thread pool")?;
+                Ok(pool.install(f))
+            }
+            None => Ok(f()),
         }
     }
 
@@ -81,6 +162,163 @@ impl Engine {
         self.parse_stats = None;
     }
 
+    /// Like `load_from_strings`, but records per-phase timings and parse
+    /// counters into `self.diagnostics`: DIT parsing and crack tagging are
+    /// timed directly, and every parsed line is counted, including malformed
+    /// ones that `parse_dit_contents` would otherwise drop silently.
+    pub fn load_from_strings_with_diagnostics(
+        &mut self,
+        dits: &[&str],
+        pots: &[&str],
+        targets: &[&str],
+    ) {
+        let dit_start = Instant::now();
+        let mut all_creds: Vec<Credential> = Vec::new();
+        let mut total_lines_parsed = 0usize;
+        let mut malformed_lines_skipped = 0usize;
+        for d in dits {
+            for line in d.lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                total_lines_parsed += 1;
+                match parse_dit_line(trimmed) {
+                    Ok(c) => all_creds.push(c),
+                    Err(_) => malformed_lines_skipped += 1,
+                }
+            }
+        }
+        let dit_parse_micros = dit_start.elapsed().as_micros() as u64;
+
+        let mut pot_merged: HashMap<String, String> = HashMap::new();
+        for p in pots {
+            pot_merged.extend(parse_pot_contents(p));
+        }
+        let mut target_names: HashSet<String> = HashSet::new();
+        for t in targets {
+            for name in parse_targets(t) {
+                target_names.insert(name.to_lowercase());
+            }
+        }
+
+        let crack_start = Instant::now();
+        for c in &mut all_creds {
+            if let Some(pw) = pot_merged.get(&c.hashtext) {
+                c.crack(pw);
+            }
+        }
+        let crack_tag_micros = crack_start.elapsed().as_micros() as u64;
+
+        let set: HashSet<Credential> = all_creds.into_iter().collect();
+        self.credentials = set.into_iter().collect();
+        for c in &mut self.credentials {
+            if target_names.contains(&c.sam_account_name.to_lowercase()) {
+                c.is_target = true;
+            }
+        }
+        self.parse_stats = None;
+        self.diagnostics = Some(Diagnostics {
+            dit_parse_micros,
+            crack_tag_micros,
+            total_lines_parsed,
+            malformed_lines_skipped,
+        });
+    }
+
+    /// Load Unix account entries from `/etc/passwd`- and `/etc/shadow`-
+    /// formatted strings, joined by username via `shadow::parse_shadow_contents`.
+    /// Unlike `load_from_strings`, this merges into whatever credentials are
+    /// already loaded (e.g. from a prior NTDS load) rather than replacing
+    /// them, so a single `Engine` can report on mixed Windows/Linux
+    /// environments. Re-runs the same crack-tagging/dedup pass used
+    /// elsewhere, against passwords already merged from potfiles.
+    pub fn load_from_shadow_strings(&mut self, passwd: &[&str], shadow: &[&str]) {
+        let mut new_creds: Vec<Credential> = Vec::new();
+        for (p, s) in passwd.iter().zip(shadow.iter()) {
+            new_creds.extend(crate::shadow::parse_shadow_contents(p, s));
+        }
+
+        let mut all_creds = std::mem::take(&mut self.credentials);
+        all_creds.extend(new_creds);
+        let set: HashSet<Credential> = all_creds.into_iter().collect();
+        self.credentials = set.into_iter().collect();
+        self.parse_stats = None;
+    }
+
+    /// Streaming file-path variant of `load_from_shadow_strings`: reads whole
+    /// passwd/shadow files into memory (these are small relative to NTDS
+    /// dumps, so no mmap/streaming is warranted) and merges the result the
+    /// same way.
+    pub fn load_from_shadow_file_paths<P: AsRef<Path>>(
+        &mut self,
+        passwd_paths: &[P],
+        shadow_paths: &[P],
+    ) -> Result<()> {
+        let mut new_creds: Vec<Credential> = Vec::new();
+        for (pp, sp) in passwd_paths.iter().zip(shadow_paths.iter()) {
+            let passwd = std::fs::read_to_string(pp)
+                .with_context(|| format!("read {}", pp.as_ref().display()))?;
+            let shadow = std::fs::read_to_string(sp)
+                .with_context(|| format!("read {}", sp.as_ref().display()))?;
+            new_creds.extend(crate::shadow::parse_shadow_contents(&passwd, &shadow));
+        }
+
+        let mut all_creds = std::mem::take(&mut self.credentials);
+        all_creds.extend(new_creds);
+        let set: HashSet<Credential> = all_creds.into_iter().collect();
+        self.credentials = set.into_iter().collect();
+        self.parse_stats = None;
+        Ok(())
+    }
+
+    /// Expand group-membership input (see `groups::parse_group_members`)
+    /// against the already-loaded credentials and flag every account whose
+    /// `sam_account_name` belongs to one of `self.config.admin_groups` as a
+    /// target, recording the driving group name in `target_reasons`.
+    /// Composes with the existing case-insensitive flat-target-list matching
+    /// in the `_with_threshold` loaders; call this after loading credentials.
+    pub fn mark_sensitive_group_targets(&mut self, group_membership: &[&str]) {
+        let mut members: HashMap<String, String> = HashMap::new();
+        for g in group_membership {
+            members.extend(crate::groups::parse_group_members(
+                g,
+                &self.config.admin_groups,
+            ));
+        }
+        let members_lower: HashMap<String, String> = members
+            .into_iter()
+            .map(|(name, group)| (name.to_lowercase(), group))
+            .collect();
+
+        for c in &mut self.credentials {
+            if let Some(group) = members_lower.get(&c.sam_account_name.to_lowercase()) {
+                c.is_target = true;
+                if !c.target_reasons.contains(group) {
+                    c.target_reasons.push(group.clone());
+                }
+            }
+        }
+    }
+
+    /// File-path variant of `mark_sensitive_group_targets`: reads whole
+    /// group-membership files into memory (small relative to NTDS dumps).
+    pub fn mark_sensitive_group_targets_from_paths<P: AsRef<Path>>(
+        &mut self,
+        group_paths: &[P],
+    ) -> Result<()> {
+        let mut contents: Vec<String> = Vec::new();
+        for p in group_paths {
+            contents.push(
+                std::fs::read_to_string(p)
+                    .with_context(|| format!("read {}", p.as_ref().display()))?,
+            );
+        }
+        let refs: Vec<&str> = contents.iter().map(|s| s.as_str()).collect();
+        self.mark_sensitive_group_targets(&refs);
+        Ok(())
+    }
+
     /// Streamingly load from file paths using line iterators and optional mmap.
     /// Parses DIT, POT, and Target files in a memory-efficient way.
     pub fn load_from_file_paths_with_threshold<P: AsRef<Path>>(
@@ -90,51 +328,57 @@ impl Engine {
         target_paths: &[P],
         mmap_threshold_bytes: u64,
     ) -> Result<()> {
+        use crate::io::for_each_line;
         use std::collections::{HashMap, HashSet};
+        use std::ops::ControlFlow;
         let mut all_creds: Vec<Credential> = Vec::new();
-        // DIT: parse line-by-line
+        let mut codecs: HashMap<String, String> = HashMap::new();
+        // DIT: parse line-by-line. `for_each_line` hands us borrowed `&str`
+        // slices, so the common well-formed-UTF-8 line costs no allocation
+        // beyond the `Credential` it parses into.
         let mut dit_malformed = 0usize;
         for p in dit_paths {
-            let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-            for line in iter.flatten() {
+            let codec = for_each_line(p, mmap_threshold_bytes, |line| {
                 let trimmed = line.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                if let Ok(c) = parse_dit_line(trimmed) {
-                    all_creds.push(c);
-                } else {
-                    dit_malformed += 1;
+                if !trimmed.is_empty() {
+                    match parse_dit_line(trimmed) {
+                        Ok(c) => all_creds.push(c),
+                        Err(_) => dit_malformed += 1,
+                    }
                 }
-            }
+                ControlFlow::Continue(())
+            })?;
+            codecs.insert(p.as_ref().display().to_string(), codec.to_string());
         }
         // POT: merge to hashmap
         let mut pot_merged: HashMap<String, String> = HashMap::new();
         let mut pot_malformed = 0usize;
         for p in pot_paths {
-            let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-            for line in iter.flatten() {
+            let codec = for_each_line(p, mmap_threshold_bytes, |line| {
                 let s = line.trim();
-                if s.is_empty() {
-                    continue;
-                }
-                if let Ok((h, pw)) = crate::pot::parse_pot_line(s) {
-                    pot_merged.insert(h, pw);
-                } else {
-                    pot_malformed += 1;
+                if !s.is_empty() {
+                    match crate::pot::parse_pot_line(s) {
+                        Ok((h, pw)) => {
+                            pot_merged.insert(h, pw);
+                        }
+                        Err(_) => pot_malformed += 1,
+                    }
                 }
-            }
+                ControlFlow::Continue(())
+            })?;
+            codecs.insert(p.as_ref().display().to_string(), codec.to_string());
         }
         // Targets: collect names lowercase
         let mut target_names: HashSet<String> = HashSet::new();
         for p in target_paths {
-            let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-            for line in iter.flatten() {
+            let codec = for_each_line(p, mmap_threshold_bytes, |line| {
                 let name = line.trim();
                 if !name.is_empty() {
                     target_names.insert(name.to_lowercase());
                 }
-            }
+                ControlFlow::Continue(())
+            })?;
+            codecs.insert(p.as_ref().display().to_string(), codec.to_string());
         }
         // Crack
         for c in &mut all_creds {
@@ -150,7 +394,11 @@ impl Engine {
                 c.is_target = true;
             }
         }
-        self.parse_stats = Some(ParseStats { dit_malformed, pot_malformed });
+        self.parse_stats = Some(ParseStats {
+            dit_malformed,
+            pot_malformed,
+            codecs,
+        });
         Ok(())
     }
 
@@ -165,100 +413,272 @@ impl Engine {
         target_paths: &[P],
         mmap_threshold_bytes: u64,
     ) -> Result<()> {
-        use crate::io::iter_lines_auto;
+        use crate::io::for_each_line;
         use rayon::prelude::*;
+        use std::ops::ControlFlow;
 
-        // DIT: parse lines per file in parallel, then flatten
-        let dit_malformed = std::sync::atomic::AtomicUsize::new(0);
-        let all_creds: Vec<Credential> = dit_paths
-            .par_iter()
-            .map(|p| -> Result<Vec<Credential>> {
-                let mut v = Vec::new();
-                let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-                for line in iter.flatten() {
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    match crate::dit::parse_dit_line(trimmed) {
-                        Ok(c) => v.push(c),
-                        Err(_) => {
-                            dit_malformed.fetch_add(
-                                1,
-                                std::sync::atomic::Ordering::Relaxed,
-                            );
-                        }
+        let threads = self.threads;
+        let (all_creds, pot_merged, target_names, dit_malformed, pot_malformed) =
+            Self::run_on_pool(threads, || -> Result<_> {
+                // DIT: parse lines per file in parallel, then flatten
+                let dit_malformed = std::sync::atomic::AtomicUsize::new(0);
+                let all_creds: Vec<Credential> = dit_paths
+                    .par_iter()
+                    .map(|p| -> Result<Vec<Credential>> {
+                        let mut v = Vec::new();
+                        for_each_line(p, mmap_threshold_bytes, |line| {
+                            let trimmed = line.trim();
+                            if !trimmed.is_empty() {
+                                match crate::dit::parse_dit_line(trimmed) {
+                                    Ok(c) => v.push(c),
+                                    Err(_) => {
+                                        dit_malformed.fetch_add(
+                                            1,
+                                            std::sync::atomic::Ordering::Relaxed,
+                                        );
+                                    }
+                                }
+                            }
+                            ControlFlow::Continue(())
+                        })?;
+                        Ok(v)
+                    })
+                    .try_reduce(Vec::new, |mut acc, mut next| {
+                        acc.append(&mut next);
+                        Ok(acc)
+                    })?;
+
+                // POT: merge maps in parallel
+                let pot_malformed = std::sync::atomic::AtomicUsize::new(0);
+                let pot_vecs: Vec<Vec<(String, String)>> = pot_paths
+                    .par_iter()
+                    .map(|p| -> Result<Vec<(String, String)>> {
+                        let mut v = Vec::new();
+                        for_each_line(p, mmap_threshold_bytes, |line| {
+                            let s = line.trim();
+                            if !s.is_empty() {
+                                match crate::pot::parse_pot_line(s) {
+                                    Ok((h, pw)) => v.push((h, pw)),
+                                    Err(_) => {
+                                        pot_malformed.fetch_add(
+                                            1,
+                                            std::sync::atomic::Ordering::Relaxed,
+                                        );
+                                    }
+                                }
+                            }
+                            ControlFlow::Continue(())
+                        })?;
+                        Ok(v)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let mut pot_merged: HashMap<String, String> = HashMap::new();
+                for v in pot_vecs {
+                    for (h, pw) in v {
+                        pot_merged.insert(h, pw);
                     }
                 }
-                Ok(v)
-            })
-            .try_reduce(Vec::new, |mut acc, mut next| {
-                acc.append(&mut next);
-                Ok(acc)
-            })?;
 
-        // POT: merge maps in parallel
-        let pot_malformed = std::sync::atomic::AtomicUsize::new(0);
-        let pot_vecs: Vec<Vec<(String, String)>> = pot_paths
-            .par_iter()
-            .map(|p| -> Result<Vec<(String, String)>> {
-                let mut v = Vec::new();
-                let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-                for line in iter.flatten() {
-                    let s = line.trim();
-                    if s.is_empty() {
-                        continue;
-                    }
-                    match crate::pot::parse_pot_line(s) {
-                        Ok((h, pw)) => v.push((h, pw)),
-                        Err(_) => {
-                            pot_malformed.fetch_add(
-                                1,
-                                std::sync::atomic::Ordering::Relaxed,
-                            );
+                // Targets: collect names lowercase in parallel
+                let target_sets: Vec<HashSet<String>> = target_paths
+                    .par_iter()
+                    .map(|p| -> Result<HashSet<String>> {
+                        let mut s = HashSet::new();
+                        for_each_line(p, mmap_threshold_bytes, |line| {
+                            let name = line.trim();
+                            if !name.is_empty() {
+                                s.insert(name.to_lowercase());
+                            }
+                            ControlFlow::Continue(())
+                        })?;
+                        Ok(s)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let mut target_names: HashSet<String> = HashSet::new();
+                for s in target_sets {
+                    target_names.extend(s);
+                }
+
+                Ok((
+                    all_creds,
+                    pot_merged,
+                    target_names,
+                    dit_malformed.load(std::sync::atomic::Ordering::Relaxed),
+                    pot_malformed.load(std::sync::atomic::Ordering::Relaxed),
+                ))
+            })??;
+
+        // Crack
+        let mut cracked = all_creds;
+        for c in &mut cracked {
+            if let Some(pw) = pot_merged.get(&c.hashtext) {
+                c.crack(pw);
+            }
+        }
+
+        // Dedup and mark targets
+        let set: std::collections::HashSet<Credential> = cracked.into_iter().collect();
+        self.credentials = set.into_iter().collect();
+        for c in &mut self.credentials {
+            if target_names.contains(&c.sam_account_name.to_lowercase()) {
+                c.is_target = true;
+            }
+        }
+        self.parse_stats = Some(ParseStats {
+            dit_malformed,
+            pot_malformed,
+            codecs: HashMap::new(),
+        });
+        Ok(())
+    }
+
+    /// Intra-file parallel variant for a single huge mmap'd DIT file: instead
+    /// of splitting work across *files* like
+    /// `load_from_file_paths_parallel_with_threshold`, this splits work
+    /// across line-aligned byte ranges *within* each DIT file (see
+    /// `io::line_aligned_segments`) and parses the ranges concurrently. POT
+    /// and target files, which are typically much smaller, are still read
+    /// sequentially. Falls back to a single segment (no parallelism) for any
+    /// DIT file smaller than `mmap_threshold_bytes` or when `workers <= 1`.
+    ///
+    /// As a byproduct of merging the per-segment results, `self.hash_occurrences`
+    /// is populated with an occurrence count per `hashtext`.
+    pub fn load_from_file_paths_parallel_chunked_with_threshold<P: AsRef<Path>>(
+        &mut self,
+        dit_paths: &[P],
+        pot_paths: &[P],
+        target_paths: &[P],
+        mmap_threshold_bytes: u64,
+        workers: usize,
+    ) -> Result<()> {
+        use crate::io::{for_each_line, line_aligned_segments};
+        use rayon::prelude::*;
+        use std::ops::ControlFlow;
+
+        let mut all_creds: Vec<Credential> = Vec::new();
+        let mut hash_occurrences: HashMap<String, usize> = HashMap::new();
+        let mut dit_malformed = 0usize;
+        for p in dit_paths {
+            let path = p.as_ref();
+            let meta = std::fs::metadata(path)?;
+            // A compressed file can't be line-aligned-segmented without first
+            // decompressing it, so route it through the same transparent
+            // gzip/zip/zstd-aware `for_each_line` the sequential loader uses
+            // instead of mmap-ing the raw (still-compressed) bytes.
+            let compressed = crate::io::sniff_codec(path)? != crate::io::Codec::None;
+            let use_mmap = !compressed
+                && meta.is_file()
+                && crate::io::should_use_mmap(meta.len(), mmap_threshold_bytes)
+                && workers > 1;
+            if !use_mmap {
+                let codec = for_each_line(path, mmap_threshold_bytes, |line| {
+                    let trimmed = line.trim();
+                    if !trimmed.is_empty() {
+                        match parse_dit_line(trimmed) {
+                            Ok(c) => {
+                                *hash_occurrences.entry(c.hashtext.clone()).or_insert(0) += 1;
+                                all_creds.push(c);
+                            }
+                            Err(_) => dit_malformed += 1,
                         }
                     }
-                }
-                Ok(v)
-            })
-            .collect::<Result<Vec<_>>>()?;
-        let mut pot_merged: HashMap<String, String> = HashMap::new();
-        for v in pot_vecs {
-            for (h, pw) in v {
-                pot_merged.insert(h, pw);
+                    ControlFlow::Continue(())
+                })?;
+                let _ = codec;
+                continue;
+            }
+
+            let file = std::fs::File::open(path)?;
+            let mmap = unsafe { memmap2::Mmap::map(&file) }?;
+            let data: &[u8] = &mmap;
+            let segments = line_aligned_segments(data, workers);
+            let threads = self.threads;
+            let (creds, occurrences, malformed): (
+                Vec<Credential>,
+                HashMap<String, usize>,
+                usize,
+            ) = Self::run_on_pool(threads, || {
+                segments
+                    .par_iter()
+                    .map(|&(start, end)| {
+                        let mut v = Vec::new();
+                        let mut occ: HashMap<String, usize> = HashMap::new();
+                        let mut malformed = 0usize;
+                        for line in data[start..end].split(|&b| b == b'\n') {
+                            let mut slice = line;
+                            if slice.ends_with(b"\r") {
+                                slice = &slice[..slice.len() - 1];
+                            }
+                            if slice.is_empty() {
+                                continue;
+                            }
+                            let s = String::from_utf8_lossy(slice);
+                            let trimmed = s.trim();
+                            if trimmed.is_empty() {
+                                continue;
+                            }
+                            match parse_dit_line(trimmed) {
+                                Ok(c) => {
+                                    *occ.entry(c.hashtext.clone()).or_insert(0) += 1;
+                                    v.push(c);
+                                }
+                                Err(_) => malformed += 1,
+                            }
+                        }
+                        (v, occ, malformed)
+                    })
+                    .reduce(
+                        || (Vec::new(), HashMap::new(), 0usize),
+                        |mut acc, next| {
+                            acc.0.extend(next.0);
+                            for (h, n) in next.1 {
+                                *acc.1.entry(h).or_insert(0) += n;
+                            }
+                            acc.2 += next.2;
+                            acc
+                        },
+                    )
+            })?;
+            all_creds.extend(creds);
+            dit_malformed += malformed;
+            for (h, n) in occurrences {
+                *hash_occurrences.entry(h).or_insert(0) += n;
             }
         }
 
-        // Targets: collect names lowercase in parallel
-        let target_sets: Vec<HashSet<String>> = target_paths
-            .par_iter()
-            .map(|p| -> Result<HashSet<String>> {
-                let mut s = HashSet::new();
-                let iter = iter_lines_auto(p, mmap_threshold_bytes)?;
-                for line in iter.flatten() {
-                    let name = line.trim();
-                    if !name.is_empty() {
-                        s.insert(name.to_lowercase());
+        let mut pot_merged: HashMap<String, String> = HashMap::new();
+        let mut pot_malformed = 0usize;
+        for p in pot_paths {
+            for_each_line(p, mmap_threshold_bytes, |line| {
+                let s = line.trim();
+                if !s.is_empty() {
+                    match crate::pot::parse_pot_line(s) {
+                        Ok((h, pw)) => {
+                            pot_merged.insert(h, pw);
+                        }
+                        Err(_) => pot_malformed += 1,
                     }
                 }
-                Ok(s)
-            })
-            .collect::<Result<Vec<_>>>()?;
+                ControlFlow::Continue(())
+            })?;
+        }
         let mut target_names: HashSet<String> = HashSet::new();
-        for s in target_sets {
-            target_names.extend(s);
+        for p in target_paths {
+            for_each_line(p, mmap_threshold_bytes, |line| {
+                let name = line.trim();
+                if !name.is_empty() {
+                    target_names.insert(name.to_lowercase());
+                }
+                ControlFlow::Continue(())
+            })?;
         }
 
-        // Crack
-        let mut cracked = all_creds;
-        for c in &mut cracked {
+        for c in &mut all_creds {
             if let Some(pw) = pot_merged.get(&c.hashtext) {
                 c.crack(pw);
             }
         }
-
-        // Dedup and mark targets
-        let set: std::collections::HashSet<Credential> = cracked.into_iter().collect();
+        let set: HashSet<Credential> = all_creds.into_iter().collect();
         self.credentials = set.into_iter().collect();
         for c in &mut self.credentials {
             if target_names.contains(&c.sam_account_name.to_lowercase()) {
@@ -266,11 +686,14 @@ impl Engine {
             }
         }
         self.parse_stats = Some(ParseStats {
-            dit_malformed: dit_malformed.load(std::sync::atomic::Ordering::Relaxed),
-            pot_malformed: pot_malformed.load(std::sync::atomic::Ordering::Relaxed),
+            dit_malformed,
+            pot_malformed,
+            codecs: HashMap::new(),
         });
+        self.hash_occurrences = Some(hash_occurrences);
         Ok(())
     }
+
     /// Convenience wrapper that uses the default mmap threshold.
     pub fn load_from_file_paths<P: AsRef<Path>>(
         &mut self,
@@ -287,11 +710,24 @@ impl Engine {
     }
 }
 
-/// Counts of malformed/ignored lines encountered during parsing.
-#[derive(Debug, Default, Clone, Copy)]
+/// Counts of malformed/ignored lines encountered during parsing, plus the
+/// compression codec detected per input file path.
+#[derive(Debug, Default, Clone)]
 pub struct ParseStats {
     pub dit_malformed: usize,
     pub pot_malformed: usize,
+    pub codecs: HashMap<String, String>,
+}
+
+/// Per-phase timings (microseconds) and parse counters for a load run, so
+/// users analyzing huge dumps can see where time goes and whether input
+/// lines were silently rejected.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Diagnostics {
+    pub dit_parse_micros: u64,
+    pub crack_tag_micros: u64,
+    pub total_lines_parsed: usize,
+    pub malformed_lines_skipped: usize,
 }
 
 #[cfg(test)]
@@ -319,6 +755,113 @@ mod tests {
         assert_eq!(admin.cleartext, "password");
     }
 
+    #[test]
+    fn sensitive_group_membership_flags_targets_with_reason() {
+        let dit = "DOMAIN\\Alice:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\nDOMAIN\\Bob:2:aad3b435b51404eeaad3b435b51404ee:31d6cfe0d16ae931b73c59d7e0c089c1";
+        let mut e = Engine::new();
+        e.load_from_strings(&[dit], &[], &[]);
+        assert!(e.credentials.iter().all(|c| !c.is_target));
+
+        e.mark_sensitive_group_targets(&["Domain Admins:alice\nRegular Users:bob"]);
+
+        let alice = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "Alice")
+            .unwrap();
+        assert!(alice.is_target);
+        assert_eq!(alice.target_reasons, vec!["Domain Admins".to_string()]);
+        let bob = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "Bob")
+            .unwrap();
+        assert!(!bob.is_target);
+    }
+
+    #[test]
+    fn apply_config_policies_flags_pattern_targets_and_weak_passwords() {
+        let dit = "DOMAIN\\Admin:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\nDOMAIN\\User:2:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa:bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let pot = "8846f7eaee8fb117ad06bdd830b7586c:abc\nbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb:correct horse battery staple";
+        let mut e = Engine::new();
+        e.load_from_strings(&[dit], &[pot], &[]);
+
+        e.apply_config_policies().unwrap();
+
+        let admin = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "Admin")
+            .unwrap();
+        assert!(admin.is_target);
+        assert!(admin.target_reasons.iter().any(|r| r.starts_with("pattern:")));
+        assert!(admin.is_weak_password);
+
+        let user = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "User")
+            .unwrap();
+        assert!(!user.is_target);
+        assert!(!user.is_weak_password);
+    }
+
+    #[test]
+    fn with_config_overrides_admin_groups_used_by_group_marking() {
+        let dit = "DOMAIN\\Alice:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        let mut e = Engine::new().with_config(crate::config::Config {
+            admin_groups: vec!["backup operators".to_string()],
+            ..crate::config::Config::default()
+        });
+        e.load_from_strings(&[dit], &[], &[]);
+        e.mark_sensitive_group_targets(&["Backup Operators:alice\nwheel:alice"]);
+
+        let alice = &e.credentials[0];
+        assert!(alice.is_target);
+        assert_eq!(alice.target_reasons, vec!["Backup Operators".to_string()]);
+    }
+
+    #[test]
+    fn shadow_strings_merge_into_existing_credentials() {
+        let dit = "DOMAIN\\Admin:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        let mut e = Engine::new();
+        e.load_from_strings(&[dit], &[], &[]);
+        assert_eq!(e.credentials.len(), 1);
+
+        let passwd = "alice:x:1000:1000:Alice:/home/alice:/bin/bash\nroot:x:0:0:root:/root:/bin/bash\n";
+        let shadow = "alice:$6$abc$def123:19000:0:99999:7:::\nroot:!:19000:0:99999:7:::\n";
+        e.load_from_shadow_strings(&[passwd], &[shadow]);
+
+        assert_eq!(e.credentials.len(), 3);
+        let alice = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "alice")
+            .unwrap();
+        assert_eq!(alice.hashtext, "$6$abc$def123");
+        let root = e
+            .credentials
+            .iter()
+            .find(|c| c.sam_account_name == "root")
+            .unwrap();
+        assert!(root.is_hash_null);
+    }
+
+    #[test]
+    fn shadow_file_paths_merge_into_existing_credentials() {
+        let tmp = tempdir().unwrap();
+        let passwd_path = tmp.path().join("passwd");
+        let shadow_path = tmp.path().join("shadow");
+        std::fs::write(&passwd_path, "alice:x:1000:1000:Alice:/home/alice:/bin/bash\n").unwrap();
+        std::fs::write(&shadow_path, "alice:$6$abc$def123:19000:0:99999:7:::\n").unwrap();
+
+        let mut e = Engine::new();
+        e.load_from_shadow_file_paths(&[&passwd_path], &[&shadow_path])
+            .unwrap();
+        assert_eq!(e.credentials.len(), 1);
+        assert_eq!(e.credentials[0].hashtext, "$6$abc$def123");
+    }
+
     #[test]
     fn parallel_loader_matches_sequential_results() {
         let tmp = tempdir().unwrap();
@@ -364,4 +907,161 @@ mod tests {
         let par_targets = e_par.credentials.iter().filter(|c| c.is_target).count();
         assert_eq!(seq_targets, par_targets);
     }
+
+    #[test]
+    fn with_threads_pins_pool_size_and_matches_sequential_results() {
+        let tmp = tempdir().unwrap();
+        let dit1 = tmp.path().join("a.txt");
+        let dit2 = tmp.path().join("b.txt");
+        std::fs::write(
+            &dit1,
+            "DOM\\A:1:aad3b435b51404eeaad3b435b51404ee:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &dit2,
+            "DOM\\B:2:aad3b435b51404eeaad3b435b51404ee:bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\n",
+        )
+        .unwrap();
+        let no_paths: Vec<&std::path::Path> = Vec::new();
+
+        let mut e_seq = Engine::new();
+        e_seq
+            .load_from_file_paths_with_threshold(
+                &[dit1.as_path(), dit2.as_path()],
+                &no_paths,
+                &no_paths,
+                0,
+            )
+            .unwrap();
+
+        let mut e_par = Engine::new().with_threads(1);
+        e_par
+            .load_from_file_paths_parallel_with_threshold(
+                &[dit1.as_path(), dit2.as_path()],
+                &no_paths,
+                &no_paths,
+                0,
+            )
+            .unwrap();
+
+        assert_eq!(e_seq.credentials.len(), e_par.credentials.len());
+    }
+
+    #[test]
+    fn diagnostics_counts_lines_and_malformed() {
+        let dit = "DOMAIN\\Admin:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\nINVALID\nDOMAIN\\User:2:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa:bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let mut e = Engine::new();
+        e.load_from_strings_with_diagnostics(&[dit], &[], &[]);
+        let d = e.diagnostics.unwrap();
+        assert_eq!(d.total_lines_parsed, 3);
+        assert_eq!(d.malformed_lines_skipped, 1);
+        assert_eq!(e.credentials.len(), 2);
+    }
+
+    #[test]
+    fn chunked_parallel_loader_matches_sequential_results_and_counts_hashes() {
+        let tmp = tempdir().unwrap();
+        let dit = tmp.path().join("ntds.txt");
+        let pot = tmp.path().join("pot.txt");
+        let tgt = tmp.path().join("targets.txt");
+
+        let mut contents = String::new();
+        for i in 0..200 {
+            contents.push_str(&format!(
+                "DOM\\User{i}:{i}:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\n"
+            ));
+        }
+        std::fs::write(&dit, &contents).unwrap();
+        std::fs::write(&pot, "8846f7eaee8fb117ad06bdd830b7586c:password\n").unwrap();
+        std::fs::write(&tgt, "User0\n").unwrap();
+
+        let mut e_seq = Engine::new();
+        e_seq
+            .load_from_file_paths_with_threshold(&[&dit], &[&pot], &[&tgt], 0)
+            .unwrap();
+
+        let mut e_chunked = Engine::new();
+        e_chunked
+            .load_from_file_paths_parallel_chunked_with_threshold(
+                &[&dit],
+                &[&pot],
+                &[&tgt],
+                0,
+                4,
+            )
+            .unwrap();
+
+        assert_eq!(e_seq.credentials.len(), e_chunked.credentials.len());
+        let seq_cracked = e_seq.credentials.iter().filter(|c| c.is_cracked).count();
+        let chunked_cracked = e_chunked.credentials.iter().filter(|c| c.is_cracked).count();
+        assert_eq!(seq_cracked, chunked_cracked);
+
+        // All 200 lines shared one hashtext, so the occurrence map should
+        // reflect that even though dedup collapses the credential list.
+        let occ = e_chunked.hash_occurrences.unwrap();
+        assert_eq!(
+            occ.get("8846f7eaee8fb117ad06bdd830b7586c").copied(),
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn chunked_loader_decompresses_gzip_instead_of_mmapping_raw_bytes() {
+        use std::io::Write as _;
+        let tmp = tempdir().unwrap();
+        let dit_gz = tmp.path().join("ntds.txt.gz");
+        {
+            let file = std::fs::File::create(&dit_gz).unwrap();
+            let mut enc =
+                flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            enc.write_all(
+                b"DOM\\U:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\n",
+            )
+            .unwrap();
+            enc.finish().unwrap();
+        }
+        let no_paths: Vec<&std::path::Path> = Vec::new();
+
+        let mut e = Engine::new();
+        // threshold 0 forces the mmap-eligible branch for plain files, but a
+        // compressed file should still be routed through decompression.
+        e.load_from_file_paths_parallel_chunked_with_threshold(
+            &[dit_gz.as_path()],
+            &no_paths,
+            &no_paths,
+            0,
+            4,
+        )
+        .unwrap();
+        assert_eq!(e.credentials.len(), 1);
+        assert_eq!(e.credentials[0].sam_account_name, "U");
+    }
+
+    #[test]
+    fn loads_gzip_compressed_ditfile_transparently() {
+        use std::io::Write as _;
+        let tmp = tempdir().unwrap();
+        let dit_gz = tmp.path().join("ntds.txt.gz");
+        {
+            let file = std::fs::File::create(&dit_gz).unwrap();
+            let mut enc =
+                flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            enc.write_all(
+                b"DOM\\U:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\n",
+            )
+            .unwrap();
+            enc.finish().unwrap();
+        }
+        let mut e = Engine::new();
+        let no_paths: Vec<&std::path::Path> = Vec::new();
+        e.load_from_file_paths_with_threshold(&[dit_gz.as_path()], &no_paths, &no_paths, 0)
+            .unwrap();
+        assert_eq!(e.credentials.len(), 1);
+        let stats = e.parse_stats.unwrap();
+        assert_eq!(
+            stats.codecs.get(&dit_gz.display().to_string()).unwrap(),
+            "gzip"
+        );
+    }
 }
@@ -1,8 +1,16 @@
-//! Export helpers for writing results to CSV and plain text files.
+//! Export helpers for writing results to CSV, plain text, JSON Lines, and
+//! (behind the `bson-export` feature) BSON.
 //!
 //! - `save_shared_hashes_csv` writes pairs of (hash, username) only for hashes
 //!   shared by more than one account.
 //! - `save_user_pass_txt` writes `DOMAIN\\User:cleartext` for cracked entries.
+//! - `to_jsonl` writes one JSON object per `Credential` per line, for feeding
+//!   downstream tooling (e.g. BloodHound-style ingest pipelines).
+//! - `to_bson` (feature `bson-export`) writes the same credentials as a
+//!   stream of BSON documents, binary-safe for cleartexts that may contain
+//!   control characters or invalid UTF-8.
+//! - `to_xlsx` (feature `xlsx-export`) writes a presentation-ready, multi-sheet
+//!   workbook for client deliverables.
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
@@ -10,23 +18,27 @@ use std::path::Path;
 use anyhow::Result;
 use csv::Writer;
 
+use crate::credential::HashType;
 use crate::engine::Engine;
 
 pub fn save_shared_hashes_csv<P: AsRef<Path>>(engine: &Engine, path: P) -> Result<()> {
-    let mut map: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    let mut map: std::collections::HashMap<String, (HashType, Vec<String>)> =
+        std::collections::HashMap::new();
     for c in &engine.credentials {
         if !c.is_hash_null && !c.hashtext.is_empty() {
-            map.entry(c.hashtext.clone())
-                .or_default()
-                .push(c.down_level_logon_name.clone());
+            let entry = map
+                .entry(c.hashtext.clone())
+                .or_insert_with(|| (c.hash_type, Vec::new()));
+            entry.1.push(c.down_level_logon_name.clone());
         }
     }
     let mut wtr = Writer::from_path(path)?;
-    wtr.write_record(["Hash", "Username"])?;
-    for (hash, users) in map.into_iter() {
+    wtr.write_record(["Hash", "HashType", "Username"])?;
+    for (hash, (hash_type, users)) in map.into_iter() {
         if users.len() > 1 {
+            let hash_type = hash_type.to_string();
             for u in users {
-                wtr.write_record([hash.as_str(), u.as_str()])?;
+                wtr.write_record([hash.as_str(), hash_type.as_str(), u.as_str()])?;
             }
         }
     }
@@ -44,6 +56,152 @@ pub fn save_user_pass_txt<P: AsRef<Path>>(engine: &Engine, path: P) -> Result<()
     Ok(())
 }
 
+/// Write every loaded `Credential` as one serde-serialized JSON object per
+/// line (account name, hashes, crack status, target flags). Downstream
+/// tooling can stream this without loading the whole export into memory.
+pub fn to_jsonl<P: AsRef<Path>>(engine: &Engine, path: P) -> Result<()> {
+    let mut f = File::create(path)?;
+    for c in &engine.credentials {
+        writeln!(f, "{}", serde_json::to_string(c)?)?;
+    }
+    Ok(())
+}
+
+/// Write every loaded `Credential` as a stream of BSON documents. Each
+/// document is self-length-prefixed, so the file is a plain concatenation
+/// with no extra framing, and binary-safe for cleartexts that aren't valid
+/// UTF-8. Gated behind the `bson-export` feature so the CSV/TXT/JSONL path
+/// stays free of the extra dependency.
+#[cfg(feature = "bson-export")]
+pub fn to_bson<P: AsRef<Path>>(engine: &Engine, path: P) -> Result<()> {
+    let mut f = File::create(path)?;
+    for c in &engine.credentials {
+        let doc = bson::to_document(c)?;
+        doc.to_writer(&mut f)?;
+    }
+    Ok(())
+}
+
+/// Write a presentation-ready, multi-sheet XLSX workbook: a "Credentials"
+/// sheet (account, hash type, crack status, plaintext, target flags), a
+/// "Reuse Clusters" sheet (see `stats::password_reuse_clusters`), and a
+/// "Summary" sheet mirroring the terminal report's headline statistics.
+/// Cracked high-value-target rows on the credentials sheet are highlighted.
+/// Gated behind the `xlsx-export` feature so the CSV/TXT/JSONL path stays
+/// free of the extra dependency.
+#[cfg(feature = "xlsx-export")]
+pub fn to_xlsx<P: AsRef<Path>>(engine: &Engine, path: P) -> Result<()> {
+    use rust_xlsxwriter::{Format, FormatAlign, Workbook};
+
+    let mut workbook = Workbook::new();
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color("#D9D9D9")
+        .set_align(FormatAlign::Center);
+    let highlight_format = Format::new()
+        .set_background_color("#FFC7CE")
+        .set_font_color("#9C0006");
+
+    // Credentials sheet
+    let creds_sheet = workbook.add_worksheet().set_name("Credentials")?;
+    let cred_headers = [
+        "Account",
+        "HashType",
+        "Cracked",
+        "Plaintext",
+        "Target",
+        "TargetReasons",
+    ];
+    for (col, h) in cred_headers.iter().enumerate() {
+        creds_sheet.write_string_with_format(0, col as u16, *h, &header_format)?;
+    }
+    for (i, c) in engine.credentials.iter().enumerate() {
+        let row = (i + 1) as u32;
+        let highlight = c.is_cracked && c.is_target;
+        let fmt = if highlight {
+            Some(&highlight_format)
+        } else {
+            None
+        };
+        write_cell(creds_sheet, row, 0, &c.down_level_logon_name, fmt)?;
+        write_cell(creds_sheet, row, 1, &c.hash_type.to_string(), fmt)?;
+        write_cell(creds_sheet, row, 2, if c.is_cracked { "Yes" } else { "No" }, fmt)?;
+        write_cell(creds_sheet, row, 3, &c.cleartext, fmt)?;
+        write_cell(creds_sheet, row, 4, if c.is_target { "Yes" } else { "No" }, fmt)?;
+        write_cell(creds_sheet, row, 5, &c.target_reasons.join(", "), fmt)?;
+    }
+
+    // Reuse Clusters sheet
+    let clusters = crate::stats::password_reuse_clusters(&engine.credentials);
+    let clusters_sheet = workbook.add_worksheet().set_name("Reuse Clusters")?;
+    let cluster_headers = ["Hash", "Accounts", "Cracked", "HasTarget"];
+    for (col, h) in cluster_headers.iter().enumerate() {
+        clusters_sheet.write_string_with_format(0, col as u16, *h, &header_format)?;
+    }
+    for (i, cluster) in clusters.iter().enumerate() {
+        let row = (i + 1) as u32;
+        let fmt = if cluster.has_target {
+            Some(&highlight_format)
+        } else {
+            None
+        };
+        write_cell(clusters_sheet, row, 0, &cluster.hashtext, fmt)?;
+        write_cell(clusters_sheet, row, 1, &cluster.accounts.join(", "), fmt)?;
+        write_cell(
+            clusters_sheet,
+            row,
+            2,
+            cluster.cracked_cleartext.as_deref().unwrap_or(""),
+            fmt,
+        )?;
+        write_cell(
+            clusters_sheet,
+            row,
+            3,
+            if cluster.has_target { "Yes" } else { "No" },
+            fmt,
+        )?;
+    }
+
+    // Summary sheet
+    let stats = crate::stats::calculate_statistics(&engine.credentials);
+    let summary_sheet = workbook.add_worksheet().set_name("Summary")?;
+    summary_sheet.write_string_with_format(0, 0, "Metric", &header_format)?;
+    summary_sheet.write_string_with_format(0, 1, "Value", &header_format)?;
+    let summary_rows: [(&str, String); 6] = [
+        ("Total creds", engine.credentials.len().to_string()),
+        ("All User Hashes", stats.user.all_count.to_string()),
+        ("All Machine Hashes", stats.machine.all_count.to_string()),
+        ("LM Cracked", stats.lm.cracked_count.to_string()),
+        ("NT Cracked", stats.nt.cracked_count.to_string()),
+        ("Reuse Clusters", clusters.len().to_string()),
+    ];
+    for (i, (label, value)) in summary_rows.iter().enumerate() {
+        let row = (i + 1) as u32;
+        summary_sheet.write_string(row, 0, *label)?;
+        summary_sheet.write_string(row, 1, value)?;
+    }
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+#[cfg(feature = "xlsx-export")]
+fn write_cell(
+    sheet: &mut rust_xlsxwriter::Worksheet,
+    row: u32,
+    col: u16,
+    value: &str,
+    format: Option<&rust_xlsxwriter::Format>,
+) -> Result<()> {
+    match format {
+        Some(f) => sheet.write_string_with_format(row, col, value, f)?,
+        None => sheet.write_string(row, col, value)?,
+    };
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,9 +221,56 @@ mod tests {
         save_user_pass_txt(&e, &txt_path).unwrap();
         let csv_content = std::fs::read_to_string(csv_path).unwrap();
         let txt_content = std::fs::read_to_string(txt_path).unwrap();
-        assert!(csv_content.contains("Hash,Username"));
+        assert!(csv_content.contains("Hash,HashType,Username"));
         assert!(csv_content.contains("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"));
         assert!(txt_content.contains("DOM\\A:pw"));
         assert!(txt_content.contains("DOM\\B:pw"));
     }
+
+    #[test]
+    fn writes_jsonl_one_object_per_credential() {
+        let mut e = Engine::new();
+        let dit = "DOM\\A:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        let pot = "8846f7eaee8fb117ad06bdd830b7586c:pw";
+        e.load_from_strings(&[dit], &[pot], &[]);
+        let dir = tempdir().unwrap();
+        let jsonl_path = dir.path().join("creds.jsonl");
+        to_jsonl(&e, &jsonl_path).unwrap();
+        let content = std::fs::read_to_string(&jsonl_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let v: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(v["sam_account_name"], "A");
+        assert_eq!(v["cleartext"], "pw");
+        assert_eq!(v["is_cracked"], true);
+        assert_eq!(v["hash_type"], "nt");
+    }
+
+    #[cfg(feature = "bson-export")]
+    #[test]
+    fn writes_bson_document_stream() {
+        let mut e = Engine::new();
+        let dit = "DOM\\A:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        e.load_from_strings(&[dit], &[], &[]);
+        let dir = tempdir().unwrap();
+        let bson_path = dir.path().join("creds.bson");
+        to_bson(&e, &bson_path).unwrap();
+        let mut f = std::fs::File::open(&bson_path).unwrap();
+        let doc = bson::Document::from_reader(&mut f).unwrap();
+        assert_eq!(doc.get_str("sam_account_name").unwrap(), "A");
+    }
+
+    #[cfg(feature = "xlsx-export")]
+    #[test]
+    fn writes_xlsx_workbook_with_three_sheets() {
+        let mut e = Engine::new();
+        let dit = "DOM\\Admin:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\nDOM\\User:2:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        let pot = "8846f7eaee8fb117ad06bdd830b7586c:pw";
+        e.load_from_strings(&[dit], &[pot], &[]);
+        let dir = tempdir().unwrap();
+        let xlsx_path = dir.path().join("report.xlsx");
+        to_xlsx(&e, &xlsx_path).unwrap();
+        assert!(xlsx_path.exists());
+        assert!(std::fs::metadata(&xlsx_path).unwrap().len() > 0);
+    }
 }
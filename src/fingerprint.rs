@@ -0,0 +1,109 @@
+//! Deterministic Merkle fingerprint over a credential set.
+//!
+//! [`compute_dataset_root`] lets two runs (or two NTDS dumps) be compared for
+//! equality by hash alone: if the root matches, the underlying
+//! `down_level_logon_name`/`hashtext` pairs are identical, independent of
+//! crack state, target flags, or the order credentials were loaded in.
+use sha2::{Digest, Sha256};
+
+use crate::credential::Credential;
+
+/// Root reported for an empty credential set.
+pub const EMPTY_ROOT: [u8; 32] = [0u8; 32];
+
+/// Leaves/parent hashes combined into one parent hash per level.
+const FANOUT: usize = 16;
+
+fn leaf_hash(c: &Credential) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(c.down_level_logon_name.as_bytes());
+    hasher.update(c.hashtext.as_bytes());
+    hasher.finalize().into()
+}
+
+fn parent_hash(chunk: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for leaf in chunk {
+        hasher.update(leaf);
+    }
+    hasher.finalize().into()
+}
+
+/// Compute a reproducible, order-independent fingerprint over `creds`.
+///
+/// One leaf hash is produced per credential (over `down_level_logon_name` +
+/// `hashtext`), the leaves are sorted so the result doesn't depend on input
+/// order, then repeatedly grouped into chunks of [`FANOUT`] and folded into a
+/// parent hash, level by level, until a single root remains. An empty input
+/// yields [`EMPTY_ROOT`] and a single leaf is its own root.
+pub fn compute_dataset_root(creds: &[Credential]) -> [u8; 32] {
+    if creds.is_empty() {
+        return EMPTY_ROOT;
+    }
+    let mut level: Vec<[u8; 32]> = creds.iter().map(leaf_hash).collect();
+    level.sort_unstable();
+
+    while level.len() > 1 {
+        level = level.chunks(FANOUT).map(parent_hash).collect();
+    }
+    level[0]
+}
+
+/// Render a root as a lowercase hex string for display or comparison.
+pub fn root_to_hex(root: &[u8; 32]) -> String {
+    root.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_yields_empty_root() {
+        assert_eq!(compute_dataset_root(&[]), EMPTY_ROOT);
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let mut c = Credential::new();
+        c.fill_from_dit(
+            "DOM\\A",
+            Credential::NULL_HASH_LM,
+            "8846f7eaee8fb117ad06bdd830b7586c",
+        );
+        assert_eq!(compute_dataset_root(&[c.clone()]), leaf_hash(&c));
+    }
+
+    #[test]
+    fn root_is_order_independent() {
+        let mut a = Credential::new();
+        a.fill_from_dit(
+            "DOM\\A",
+            Credential::NULL_HASH_LM,
+            "8846f7eaee8fb117ad06bdd830b7586c",
+        );
+        let mut b = Credential::new();
+        b.fill_from_dit(
+            "DOM\\B",
+            Credential::NULL_HASH_LM,
+            "31d6cfe0d16ae931b73c59d7e0c089c1",
+        );
+        let forward = compute_dataset_root(&[a.clone(), b.clone()]);
+        let backward = compute_dataset_root(&[b, a]);
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn root_ignores_crack_state() {
+        let mut a = Credential::new();
+        a.fill_from_dit(
+            "DOM\\A",
+            Credential::NULL_HASH_LM,
+            "8846f7eaee8fb117ad06bdd830b7586c",
+        );
+        let mut cracked = a.clone();
+        cracked.crack("Password1!");
+        cracked.is_target = true;
+        assert_eq!(compute_dataset_root(&[a]), compute_dataset_root(&[cracked]));
+    }
+}
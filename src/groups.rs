@@ -0,0 +1,94 @@
+//! Parser for group-membership input, used to auto-flag members of sensitive
+//! groups as targets without hand-listing every privileged account.
+//!
+//! Accepts lines of the form `GroupName:member1,member2,...`. Blank lines and
+//! lines without a `:` separator are ignored, mirroring `targets`' tolerance
+//! for stray whitespace.
+use std::collections::{HashMap, HashSet};
+
+/// Group names (matched case-insensitively) whose members are auto-flagged
+/// as targets: Windows high-privilege groups plus the common Unix
+/// root-equivalent groups.
+pub const SENSITIVE_GROUPS: &[&str] = &["domain admins", "enterprise admins", "sudo", "wheel"];
+
+/// Whether `name` (any case) is one of [`SENSITIVE_GROUPS`].
+pub fn is_sensitive_group(name: &str) -> bool {
+    SENSITIVE_GROUPS.contains(&name.trim().to_lowercase().as_str())
+}
+
+/// Parse `GroupName:member1,member2,...` lines, keeping only members of
+/// sensitive groups. Returns a map of member name (as written) -> the
+/// sensitive group that first claimed them.
+pub fn parse_sensitive_group_members(contents: &str) -> HashMap<String, String> {
+    let allowlist: Vec<String> = SENSITIVE_GROUPS.iter().map(|s| s.to_string()).collect();
+    parse_group_members(contents, &allowlist)
+}
+
+/// Like [`parse_sensitive_group_members`], but matches against a caller-
+/// supplied (case-insensitive) group allowlist instead of the built-in
+/// [`SENSITIVE_GROUPS`] list, so `Config::admin_groups` can override it per
+/// engagement.
+pub fn parse_group_members(contents: &str, allowed_groups: &[String]) -> HashMap<String, String> {
+    let allowed_lower: HashSet<String> = allowed_groups.iter().map(|g| g.to_lowercase()).collect();
+    let mut members: HashMap<String, String> = HashMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((group, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let group = group.trim();
+        if !allowed_lower.contains(&group.to_lowercase()) {
+            continue;
+        }
+        for member in rest.split(',') {
+            let member = member.trim();
+            if member.is_empty() {
+                continue;
+            }
+            members
+                .entry(member.to_string())
+                .or_insert_with(|| group.to_string());
+        }
+    }
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_members_of_sensitive_groups_only() {
+        let contents = "Domain Admins:alice,bob\nRegular Users:carol\nwheel:dave\n";
+        let members = parse_sensitive_group_members(contents);
+        assert_eq!(members.get("alice").unwrap(), "Domain Admins");
+        assert_eq!(members.get("bob").unwrap(), "Domain Admins");
+        assert!(!members.contains_key("carol"));
+        assert_eq!(members.get("dave").unwrap(), "wheel");
+    }
+
+    #[test]
+    fn ignores_blank_and_malformed_lines() {
+        let members = parse_sensitive_group_members("\nno_colon_line\n");
+        assert!(members.is_empty());
+    }
+
+    #[test]
+    fn group_name_matching_is_case_insensitive() {
+        assert!(is_sensitive_group("SUDO"));
+        assert!(is_sensitive_group("  Wheel  "));
+        assert!(!is_sensitive_group("Users"));
+    }
+
+    #[test]
+    fn parse_group_members_honors_custom_allowlist() {
+        let contents = "Backup Operators:alice\nwheel:bob\n";
+        let allowlist = vec!["backup operators".to_string()];
+        let members = parse_group_members(contents, &allowlist);
+        assert_eq!(members.get("alice").unwrap(), "Backup Operators");
+        assert!(!members.contains_key("bob"));
+    }
+}
@@ -1,8 +1,10 @@
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Read};
+use std::ops::ControlFlow;
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
 use memmap2::Mmap;
 
 /// Threshold in bytes above which we attempt to use mmap for reading.
@@ -11,75 +13,428 @@ pub const DEFAULT_MMAP_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024; // 16 MiB
 
 pub type LineIter = Box<dyn Iterator<Item = io::Result<String>> + Send + 'static>;
 
+/// Compression codec detected for an input file. `None` means the file is
+/// treated as plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    None,
+    Gzip,
+    Zip,
+    Zstd,
+}
+
+impl std::fmt::Display for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Codec::None => "none",
+            Codec::Gzip => "gzip",
+            Codec::Zip => "zip",
+            Codec::Zstd => "zstd",
+        };
+        f.write_str(s)
+    }
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZIP_MAGIC: [u8; 4] = [b'P', b'K', 0x03, 0x04];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Sniff the compression codec of a file from its leading bytes, falling
+/// back to the file extension when the file is too short to contain a magic
+/// number or its content doesn't match a known one.
+pub fn sniff_codec<P: AsRef<Path>>(path: P) -> Result<Codec> {
+    let path = path.as_ref();
+    let mut header = [0u8; 4];
+    let read = {
+        let mut file =
+            File::open(path).with_context(|| format!("open {}", path.display()))?;
+        let mut n = 0;
+        while n < header.len() {
+            match file.read(&mut header[n..])? {
+                0 => break,
+                r => n += r,
+            }
+        }
+        n
+    };
+    if read >= 4 && header == ZSTD_MAGIC {
+        return Ok(Codec::Zstd);
+    }
+    if read >= 4 && header[..4] == ZIP_MAGIC {
+        return Ok(Codec::Zip);
+    }
+    if read >= 2 && header[..2] == GZIP_MAGIC {
+        return Ok(Codec::Gzip);
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("gz") => Ok(Codec::Gzip),
+        Some("zip") => Ok(Codec::Zip),
+        Some("zst") | Some("zstd") => Ok(Codec::Zstd),
+        _ => Ok(Codec::None),
+    }
+}
+
 /// Decide whether to use mmap based on file size and threshold.
 pub fn should_use_mmap(file_size_bytes: u64, threshold_bytes: u64) -> bool {
     file_size_bytes >= threshold_bytes
 }
 
-/// Iterate lines from a file path using buffered reader (non-mmap).
-pub fn iter_lines_bufread<P: AsRef<Path>>(path: P) -> Result<LineIter> {
-    let file = File::open(&path).with_context(|| format!("open {}", path.as_ref().display()))?;
-    let reader = BufReader::new(file);
-    let lines = reader.lines();
-    Ok(Box::new(lines))
+/// Compute `workers` disjoint, line-aligned byte ranges that together cover
+/// all of `data`. Cut points start at `i * data.len() / workers` and are
+/// advanced forward to the next `\n` boundary (via `memchr`) so no line ever
+/// straddles two ranges. A cut that lands inside one very long line (longer
+/// than a single worker's share) simply advances past it; if that makes two
+/// consecutive cuts coincide, the resulting empty range is dropped, which
+/// has the effect of merging that segment into its neighbor rather than
+/// handing a worker a zero-newline/empty range.
+pub fn line_aligned_segments(data: &[u8], workers: usize) -> Vec<(usize, usize)> {
+    if workers <= 1 || data.is_empty() {
+        return vec![(0, data.len())];
+    }
+    let mut cuts = Vec::with_capacity(workers + 1);
+    cuts.push(0usize);
+    for i in 1..workers {
+        let raw = (i * data.len()) / workers;
+        let aligned = match memchr::memchr(b'\n', &data[raw..]) {
+            Some(off) => raw + off + 1,
+            None => data.len(),
+        };
+        cuts.push(aligned);
+    }
+    cuts.push(data.len());
+    cuts.dedup();
+
+    cuts.windows(2)
+        .filter(|w| w[0] < w[1])
+        .map(|w| (w[0], w[1]))
+        .collect()
 }
 
-/// Iterate lines from a file path using mmap. This avoids copying but still
-/// allocates per-returned String; it scans for '\n' boundaries.
-pub fn iter_lines_mmap<P: AsRef<Path>>(path: P) -> Result<LineIter> {
-    let file = File::open(&path).with_context(|| format!("open {}", path.as_ref().display()))?;
-    let mmap =
-        unsafe { Mmap::map(&file) }.with_context(|| format!("mmap {}", path.as_ref().display()))?;
-    let iter = MmapLines { mmap, pos: 0 };
-    Ok(Box::new(iter))
+/// Push-style line iteration that avoids allocating a `String` per line on
+/// the common path. `f` is called once per line with a borrowed `&str`;
+/// return `ControlFlow::Break(())` to stop early. Transparently decompresses
+/// gzip/zip/zstd inputs exactly like [`iter_lines_auto`] and returns the
+/// detected [`Codec`].
+///
+/// On the mmap path, lines are `&str` slices carved directly out of the
+/// mapping via `memchr` — no copy at all for well-formed UTF-8. On the
+/// bufread path, a single growable buffer is reused across `read_until`
+/// calls instead of heap-allocating a fresh `String` per line. In both
+/// cases, a line that isn't valid UTF-8 falls back to an allocating
+/// `String::from_utf8_lossy` scratch buffer, same as the old per-line
+/// `String`-returning iterators.
+pub fn for_each_line<P: AsRef<Path>>(
+    path: P,
+    threshold_bytes: u64,
+    mut f: impl FnMut(&str) -> ControlFlow<()>,
+) -> Result<Codec> {
+    let path = path.as_ref();
+    let codec = sniff_codec(path)?;
+    match codec {
+        Codec::Gzip => {
+            let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+            for_each_line_read(GzDecoder::new(file), &mut f)?;
+        }
+        Codec::Zstd => {
+            let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+            let decoder = zstd::stream::read::Decoder::new(file)
+                .with_context(|| format!("zstd init {}", path.display()))?;
+            for_each_line_read(decoder, &mut f)?;
+        }
+        Codec::Zip => {
+            // Zip members are already fully materialized to decompress; no
+            // streaming win available here, so just drive `f` over the
+            // resulting lines.
+            for line in iter_lines_compressed(path, Codec::Zip)?.flatten() {
+                if f(&line).is_break() {
+                    break;
+                }
+            }
+        }
+        Codec::None => {
+            let meta =
+                std::fs::metadata(path).with_context(|| format!("stat {}", path.display()))?;
+            if meta.is_file() && should_use_mmap(meta.len(), threshold_bytes) {
+                for_each_line_mmap(path, &mut f)?;
+            } else {
+                let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+                for_each_line_read(file, &mut f)?;
+            }
+        }
+    }
+    Ok(codec)
 }
 
-struct MmapLines {
-    mmap: Mmap,
-    pos: usize,
+/// Zero-copy mmap half of [`for_each_line`].
+fn for_each_line_mmap(path: &Path, f: &mut impl FnMut(&str) -> ControlFlow<()>) -> Result<()> {
+    let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    let mmap =
+        unsafe { Mmap::map(&file) }.with_context(|| format!("mmap {}", path.display()))?;
+    let data: &[u8] = &mmap;
+    let mut pos = 0usize;
+    while pos < data.len() {
+        let (end, next) = match memchr::memchr(b'\n', &data[pos..]) {
+            Some(off) => (pos + off, pos + off + 1),
+            None => (data.len(), data.len()),
+        };
+        let mut slice = &data[pos..end];
+        if slice.ends_with(b"\r") {
+            slice = &slice[..slice.len() - 1];
+        }
+        let flow = match std::str::from_utf8(slice) {
+            Ok(s) => f(s),
+            Err(_) => f(&String::from_utf8_lossy(slice)),
+        };
+        pos = next;
+        if flow.is_break() {
+            break;
+        }
+    }
+    Ok(())
 }
 
-impl Iterator for MmapLines {
-    type Item = io::Result<String>;
-    fn next(&mut self) -> Option<Self::Item> {
-        let data: &[u8] = &self.mmap;
-        if self.pos >= data.len() {
-            return None;
+/// Buffered-reader half of [`for_each_line`]: reuses a single growable
+/// `Vec<u8>` across `read_until(b'\n', ..)` calls instead of allocating a
+/// fresh `String` per line.
+fn for_each_line_read<R: Read>(
+    reader: R,
+    f: &mut impl FnMut(&str) -> ControlFlow<()>,
+) -> Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf).context("read line")?;
+        if n == 0 {
+            break;
         }
-        let start = self.pos;
-        // Find next newline
-        if let Some(off) = memchr::memchr(b'\n', &data[self.pos..]) {
-            let end = self.pos + off;
-            self.pos = end + 1; // skip newline
-            Some(line_from_bytes(&data[start..end]))
-        } else {
-            // Last line without trailing newline
-            self.pos = data.len();
-            Some(line_from_bytes(&data[start..]))
+        let mut slice: &[u8] = &buf;
+        if slice.ends_with(b"\n") {
+            slice = &slice[..slice.len() - 1];
+        }
+        if slice.ends_with(b"\r") {
+            slice = &slice[..slice.len() - 1];
+        }
+        let flow = match std::str::from_utf8(slice) {
+            Ok(s) => f(s),
+            Err(_) => f(&String::from_utf8_lossy(slice)),
+        };
+        if flow.is_break() {
+            break;
         }
     }
+    Ok(())
 }
 
-fn line_from_bytes(bytes: &[u8]) -> io::Result<String> {
-    // Trim a trailing '\r' if present (handle Windows CRLF)
-    let slice = if bytes.ends_with(b"\r") {
-        &bytes[..bytes.len() - 1]
-    } else {
-        bytes
-    };
-    match std::str::from_utf8(slice) {
-        Ok(s) => Ok(s.to_string()),
-        Err(_) => Ok(String::from_utf8_lossy(slice).to_string()),
+/// Iterate lines from a file path using buffered reader (non-mmap).
+///
+/// Thin compatibility adapter over [`for_each_line`] for callers that want
+/// an owned-`String` `Iterator`; lines are collected eagerly. Prefer
+/// `for_each_line` directly on multi-gigabyte inputs to avoid the per-line
+/// allocation this adapter reintroduces.
+pub fn iter_lines_bufread<P: AsRef<Path>>(path: P) -> Result<LineIter> {
+    let mut lines: Vec<io::Result<String>> = Vec::new();
+    for_each_line(path, u64::MAX, |s| {
+        lines.push(Ok(s.to_string()));
+        ControlFlow::Continue(())
+    })?;
+    Ok(Box::new(lines.into_iter()))
+}
+
+/// Iterate lines from a file path using mmap.
+///
+/// Thin compatibility adapter over [`for_each_line`] (forced onto the mmap
+/// path via a zero threshold); lines are collected eagerly into owned
+/// `String`s. Prefer `for_each_line` directly to keep the zero-copy benefit.
+pub fn iter_lines_mmap<P: AsRef<Path>>(path: P) -> Result<LineIter> {
+    let mut lines: Vec<io::Result<String>> = Vec::new();
+    for_each_line(path, 0, |s| {
+        lines.push(Ok(s.to_string()));
+        ControlFlow::Continue(())
+    })?;
+    Ok(Box::new(lines.into_iter()))
+}
+
+/// Test-only helper mirroring the header-sniffing half of [`sniff_codec`]
+/// without touching the filesystem.
+#[cfg(test)]
+fn detect_codec_from_header(header: &[u8]) -> Codec {
+    if header.len() >= 4 && header[..4] == ZSTD_MAGIC {
+        return Codec::Zstd;
+    }
+    if header.len() >= 4 && header[..4] == ZIP_MAGIC {
+        return Codec::Zip;
     }
+    if header.len() >= 2 && header[..2] == GZIP_MAGIC {
+        return Codec::Gzip;
+    }
+    Codec::None
 }
 
-/// Choose mmap or bufread and return an iterator over lines.
+/// Decompress a zip member to lines. Only `Codec::Zip` is handled here: the
+/// gzip/zstd streaming decoders are driven directly by [`for_each_line`]'s
+/// bufread path instead, since they need no whole-file buffering.
+fn iter_lines_compressed(path: &Path, codec: Codec) -> Result<LineIter> {
+    match codec {
+        Codec::Zip => {
+            let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+            let mut archive = zip::ZipArchive::new(file)
+                .with_context(|| format!("open zip {}", path.display()))?;
+            // Decompress the single largest member; that's the dump/potfile
+            // itself for the NTDS/hashcat archives this is meant to handle.
+            let idx = (0..archive.len())
+                .max_by_key(|&i| archive.by_index(i).map(|f| f.size()).unwrap_or(0))
+                .with_context(|| format!("empty zip archive: {}", path.display()))?;
+            let mut member = archive.by_index(idx)?;
+            let mut buf = Vec::with_capacity(member.size() as usize);
+            member.read_to_end(&mut buf)?;
+            let text = String::from_utf8_lossy(&buf).into_owned();
+            let lines: Vec<io::Result<String>> =
+                text.lines().map(|l| Ok(l.to_string())).collect();
+            Ok(Box::new(lines.into_iter()))
+        }
+        other => unreachable!("iter_lines_compressed only handles Codec::Zip, got {other}"),
+    }
+}
+
+/// Choose mmap or bufread and return an iterator over lines, transparently
+/// decompressing gzip/zip/zstd inputs detected by [`sniff_codec`]. Compressed
+/// inputs always go through the streaming decoder path (mmap is skipped);
+/// plain text still uses mmap once at or above `threshold_bytes`.
+///
+/// Thin compatibility adapter over [`for_each_line`] for callers that want an
+/// owned-`String` `Iterator`; lines are collected eagerly. Prefer
+/// `for_each_line` directly on multi-gigabyte inputs.
 pub fn iter_lines_auto<P: AsRef<Path>>(path: P, threshold_bytes: u64) -> Result<LineIter> {
-    let meta =
-        std::fs::metadata(&path).with_context(|| format!("stat {}", path.as_ref().display()))?;
-    if meta.is_file() && should_use_mmap(meta.len(), threshold_bytes) {
-        iter_lines_mmap(path)
-    } else {
-        iter_lines_bufread(path)
+    iter_lines_auto_with_codec(path, threshold_bytes).map(|(iter, _)| iter)
+}
+
+/// Like [`iter_lines_auto`], but also returns the detected [`Codec`] so
+/// callers can record it (e.g. `engine::ParseStats`).
+pub fn iter_lines_auto_with_codec<P: AsRef<Path>>(
+    path: P,
+    threshold_bytes: u64,
+) -> Result<(LineIter, Codec)> {
+    let mut lines: Vec<io::Result<String>> = Vec::new();
+    let codec = for_each_line(path, threshold_bytes, |s| {
+        lines.push(Ok(s.to_string()));
+        ControlFlow::Continue(())
+    })?;
+    Ok((Box::new(lines.into_iter()), codec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_gzip_zip_zstd_and_plain_by_magic_bytes() {
+        assert_eq!(
+            detect_codec_from_header(&GZIP_MAGIC),
+            Codec::Gzip
+        );
+        assert_eq!(detect_codec_from_header(&ZIP_MAGIC), Codec::Zip);
+        assert_eq!(detect_codec_from_header(&ZSTD_MAGIC), Codec::Zstd);
+        assert_eq!(detect_codec_from_header(b"DOM\\"), Codec::None);
+    }
+
+    #[test]
+    fn gzip_round_trips_through_iter_lines_auto() {
+        use std::io::Write as _;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ntds.txt.gz");
+        {
+            let file = File::create(&path).unwrap();
+            let mut enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            enc.write_all(b"DOM\\A:1:x:y\nDOM\\B:2:x:y\n").unwrap();
+            enc.finish().unwrap();
+        }
+        let (iter, codec) = iter_lines_auto_with_codec(&path, DEFAULT_MMAP_THRESHOLD_BYTES).unwrap();
+        assert_eq!(codec, Codec::Gzip);
+        let lines: Vec<String> = iter.flatten().collect();
+        assert_eq!(lines, vec!["DOM\\A:1:x:y", "DOM\\B:2:x:y"]);
+    }
+
+    #[test]
+    fn for_each_line_handles_crlf_and_missing_trailing_newline() {
+        for threshold in [0u64, DEFAULT_MMAP_THRESHOLD_BYTES] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("ntds.txt");
+            std::fs::write(&path, b"DOM\\A:1:x:y\r\nDOM\\B:2:x:y").unwrap();
+            let mut lines = Vec::new();
+            for_each_line(&path, threshold, |s| {
+                lines.push(s.to_string());
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            assert_eq!(lines, vec!["DOM\\A:1:x:y", "DOM\\B:2:x:y"]);
+        }
+    }
+
+    #[test]
+    fn for_each_line_stops_early_on_break() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ntds.txt");
+        std::fs::write(&path, b"a\nb\nc\n").unwrap();
+        let mut lines = Vec::new();
+        for_each_line(&path, 0, |s| {
+            lines.push(s.to_string());
+            if s == "b" {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert_eq!(lines, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn line_aligned_segments_cover_every_line_exactly_once() {
+        let data = b"aa\nbb\ncc\ndd\nee\nff\ngg\n".as_slice();
+        let segments = line_aligned_segments(data, 4);
+        // Ranges must be contiguous, non-overlapping, and cover all bytes.
+        let mut pos = 0;
+        for &(start, end) in &segments {
+            assert_eq!(start, pos);
+            assert!(end <= data.len());
+            pos = end;
+        }
+        assert_eq!(pos, data.len());
+        // Every line appears exactly once across all segments, in order.
+        let lines: Vec<&[u8]> = segments
+            .iter()
+            .flat_map(|&(s, e)| data[s..e].split(|&b| b == b'\n'))
+            .filter(|l| !l.is_empty())
+            .collect();
+        assert_eq!(lines, vec![
+            b"aa".as_slice(),
+            b"bb",
+            b"cc",
+            b"dd",
+            b"ee",
+            b"ff",
+            b"gg",
+        ]);
+    }
+
+    #[test]
+    fn line_aligned_segments_handles_one_giant_line() {
+        let data = b"x".repeat(100);
+        let segments = line_aligned_segments(&data, 8);
+        // No newlines at all: every cut advances straight to EOF, so only
+        // one (non-empty) segment should survive.
+        assert_eq!(segments, vec![(0, 100)]);
+    }
+
+    #[test]
+    fn iter_lines_bufread_and_mmap_agree_on_the_same_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ntds.txt");
+        std::fs::write(&path, b"DOM\\A:1:x:y\nDOM\\B:2:x:y\n").unwrap();
+        let bufread: Vec<String> = iter_lines_bufread(&path).unwrap().flatten().collect();
+        let mmap: Vec<String> = iter_lines_mmap(&path).unwrap().flatten().collect();
+        assert_eq!(bufread, mmap);
     }
 }
+
@@ -11,8 +11,15 @@
 //! - `io` offers streaming and mmap-backed line iteration for large files
 //! - `engine` coordinates parsing, cracking, deduplication, and target tagging
 //! - `stats` computes aggregate statistics
+//! - `fingerprint` computes a deterministic Merkle root over a credential set
+//! - `shadow` parses Unix `/etc/passwd`+`/etc/shadow` account entries
+//! - `groups` parses group-membership input and flags sensitive-group members
+//! - `config` loads layered TOML/CLI settings for target marking and
+//!   password policy
+//! - `locale` loads Fluent `.ftl` bundles for localized report text
 //! - `report` renders a colored terminal summary
-//! - `export` persists CSV/TXT outputs
+//! - `export` persists CSV/TXT/JSONL outputs (BSON behind the `bson-export`
+//!   feature, XLSX behind `xlsx-export`)
 //!
 //! Most applications should construct an `engine::Engine`, load input files (or
 //! strings for tests), then either render a summary via `report::render_summary`
@@ -29,13 +36,18 @@
 //! # Ok(())
 //! # }
 //! ```
+pub mod config;
 pub mod credential;
 pub mod dit;
 pub mod engine;
 pub mod export;
+pub mod fingerprint;
+pub mod groups;
 pub mod io;
+pub mod locale;
 pub mod pot;
 pub mod report;
+pub mod shadow;
 pub mod stats;
 pub mod targets;
 
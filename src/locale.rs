@@ -0,0 +1,116 @@
+//! Fluent-based localization for `report`'s rendered summary text.
+//!
+//! Ships a built-in English bundle (`locales/en.ftl`, embedded via
+//! `include_str!`) and supports loading an additional locale's `.ftl`
+//! bundle at runtime (see [`Localizer::new`]). Message IDs missing from the
+//! selected locale fall back to the English bundle, so a partial
+//! translation still renders something instead of an empty string. Numeric
+//! counts are passed as Fluent arguments so plural rules (e.g. "1 account"
+//! vs "N accounts") are resolved per-locale rather than hand-rolled per
+//! string.
+use anyhow::{Context, Result, anyhow};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+const BUILTIN_EN_FTL: &str = include_str!("locales/en.ftl");
+
+fn build_bundle(lang: LanguageIdentifier, source: &str) -> Result<FluentBundle<FluentResource>> {
+    let resource = FluentResource::try_new(source.to_string())
+        .map_err(|(_, errs)| anyhow!("failed to parse Fluent resource: {errs:?}"))?;
+    let mut bundle = FluentBundle::new(vec![lang]);
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .map_err(|errs| anyhow!("failed to add Fluent resource: {errs:?}"))?;
+    Ok(bundle)
+}
+
+/// Loads and queries localized report strings, falling back to the built-in
+/// English bundle for any message ID the selected locale doesn't define.
+pub struct Localizer {
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    /// Build a localizer for `locale` (a BCP-47 tag, e.g. `"en"`, `"es"`).
+    /// `custom_ftl` is that locale's `.ftl` source (read from disk by the
+    /// caller, e.g. a `--locale-file` CLI flag); pass `None` to render in
+    /// the built-in English bundle.
+    pub fn new(locale: &str, custom_ftl: Option<&str>) -> Result<Localizer> {
+        let lang: LanguageIdentifier = locale
+            .parse()
+            .with_context(|| format!("invalid locale tag {locale}"))?;
+        let fallback = build_bundle("en".parse().unwrap(), BUILTIN_EN_FTL)?;
+        let bundle = match custom_ftl {
+            Some(src) => build_bundle(lang, src)?,
+            None => build_bundle(lang, BUILTIN_EN_FTL)?,
+        };
+        Ok(Localizer { bundle, fallback })
+    }
+
+    /// Format message `id` with `args`, falling back to the English bundle
+    /// if `id` isn't defined in the selected locale, and to the bare id if
+    /// neither bundle defines it.
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        if let Some(msg) = self.bundle.get_message(id).and_then(|m| m.value()) {
+            let mut errs = Vec::new();
+            return self.bundle.format_pattern(msg, args, &mut errs).into_owned();
+        }
+        if let Some(msg) = self.fallback.get_message(id).and_then(|m| m.value()) {
+            let mut errs = Vec::new();
+            return self
+                .fallback
+                .format_pattern(msg, args, &mut errs)
+                .into_owned();
+        }
+        id.to_string()
+    }
+
+    /// Convenience for a message with a single numeric `count` argument, for
+    /// plural-sensitive strings like `accounts-count`.
+    pub fn format_count(&self, id: &str, count: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set("count", FluentValue::from(count as i64));
+        self.format(id, Some(&args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_english_bundle_formats_known_messages() {
+        let loc = Localizer::new("en", None).unwrap();
+        assert_eq!(loc.format("section-high-value-targets", None), "High-Value Targets");
+    }
+
+    #[test]
+    fn accounts_count_pluralizes_by_locale_rules() {
+        let loc = Localizer::new("en", None).unwrap();
+        assert_eq!(loc.format_count("accounts-count", 1), "1 Account");
+        assert_eq!(loc.format_count("accounts-count", 3), "3 Accounts");
+    }
+
+    #[test]
+    fn custom_locale_falls_back_to_english_for_missing_keys() {
+        let custom = "section-high-value-targets = Objetivos de Alto Valor\n";
+        let loc = Localizer::new("es", Some(custom)).unwrap();
+        assert_eq!(
+            loc.format("section-high-value-targets", None),
+            "Objetivos de Alto Valor"
+        );
+        // not defined in the custom bundle, falls back to English
+        assert_eq!(
+            loc.format("section-password-hash-stats", None),
+            "Password Hash Statistics"
+        );
+    }
+
+    #[test]
+    fn unknown_message_id_falls_back_to_the_bare_id() {
+        let loc = Localizer::new("en", None).unwrap();
+        assert_eq!(loc.format("no-such-message", None), "no-such-message");
+    }
+}
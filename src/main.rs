@@ -1,9 +1,10 @@
 //! CLI entrypoint for `tattletale`.
 //!
 //! Parses command-line arguments, validates input files, loads data through the
-//! library engine with optional mmap threshold selection, prints a terminal
-//! summary, and optionally writes CSV/TXT exports when an output directory is
-//! provided.
+//! library engine with optional mmap threshold selection, optionally merges
+//! Unix passwd/shadow accounts and group-membership target flagging, prints a
+//! terminal summary, and optionally writes CSV/TXT exports (plus `--format`
+//! JSONL/BSON/XLSX) when an output directory is provided.
 use std::fs;
 use std::path::PathBuf;
 
@@ -12,11 +13,16 @@ use clap::{Parser, ValueEnum};
 use colored::Colorize;
 use log::{LevelFilter, error, warn};
 use tattletale::{
+    config::{CliOverrides, Config},
     engine::Engine,
-    export::{save_shared_hashes_csv, save_user_pass_txt},
+    export::{save_shared_hashes_csv, save_user_pass_txt, to_jsonl},
     io::DEFAULT_MMAP_THRESHOLD_BYTES,
-    report::render_summary_with_top,
+    report::{render_summary_localized, render_summary_with_diagnostics},
 };
+#[cfg(feature = "bson-export")]
+use tattletale::export::to_bson;
+#[cfg(feature = "xlsx-export")]
+use tattletale::export::to_xlsx;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -68,6 +74,61 @@ struct Args {
     /// Suppress summary output (still writes exports if -o is provided)
     #[arg(short = 'q', long = "quiet")]
     quiet: bool,
+
+    /// Path to a TOML config file overriding target patterns, admin groups,
+    /// and password policy (see `config::Config`)
+    #[arg(long = "config")]
+    config: Option<PathBuf>,
+
+    /// Override `min_password_length` from the config file/defaults
+    #[arg(long = "min-password-length")]
+    min_password_length: Option<usize>,
+
+    /// Append a Diagnostics section with load/render phase timings and
+    /// counters to the printed summary
+    #[arg(long = "diagnostics")]
+    diagnostics: bool,
+
+    /// BCP-47 locale tag for the rendered summary (e.g. "es"). Defaults to
+    /// the built-in English bundle.
+    #[arg(long = "locale", default_value = "en")]
+    locale: String,
+
+    /// Path to a `.ftl` file providing `--locale`'s message bundle. Message
+    /// IDs it doesn't define fall back to the built-in English bundle.
+    #[arg(long = "locale-file")]
+    locale_file: Option<PathBuf>,
+
+    /// Path to a Unix `/etc/passwd` file to merge as additional accounts.
+    /// Pairs positionally with `--shadow-file` (first --passwd-file with
+    /// first --shadow-file, and so on); must be given the same number of
+    /// times as `--shadow-file`.
+    #[arg(long = "passwd-file")]
+    passwd_files: Vec<PathBuf>,
+
+    /// Path to a Unix `/etc/shadow` file to merge as additional accounts.
+    /// Pairs positionally with `--passwd-file`.
+    #[arg(long = "shadow-file")]
+    shadow_files: Vec<PathBuf>,
+
+    /// Path to a group-membership file (see `groups::parse_group_members`)
+    /// used to flag members of `Config::admin_groups` as targets
+    #[arg(long = "groups-file")]
+    groups_files: Vec<PathBuf>,
+
+    /// Additional export format(s) to write to `--output` besides the
+    /// default CSV/TXT (may be given more than once)
+    #[arg(long = "format", value_enum)]
+    formats: Vec<ExportFormat>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Jsonl,
+    #[cfg(feature = "bson-export")]
+    Bson,
+    #[cfg(feature = "xlsx-export")]
+    Xlsx,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -146,7 +207,21 @@ fn main() {
         error!("{}", e);
         std::process::exit(2);
     }
-    let mut engine = Engine::new();
+    let file_config = match &args.config {
+        Some(path) => match Config::from_file(path) {
+            Ok(c) => c,
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(7);
+            }
+        },
+        None => Config::default(),
+    };
+    let config = file_config.with_cli_overrides(CliOverrides {
+        min_password_length: args.min_password_length,
+        ..Default::default()
+    });
+    let mut engine = Engine::new().with_config(config);
     let threshold = if args.mmap_threshold == 0 {
         u64::MAX
     } else {
@@ -184,11 +259,62 @@ fn main() {
         error!("failed to load inputs: {}", e);
         std::process::exit(3);
     }
+    if args.passwd_files.len() != args.shadow_files.len() {
+        error!("--passwd-file and --shadow-file must be given the same number of times (they pair positionally)");
+        std::process::exit(11);
+    }
+    if !args.passwd_files.is_empty() {
+        if let Err(e) =
+            engine.load_from_shadow_file_paths(&args.passwd_files, &args.shadow_files)
+        {
+            error!("failed to load shadow accounts: {}", e);
+            std::process::exit(12);
+        }
+    }
+
+    if !args.groups_files.is_empty() {
+        if let Err(e) = engine.mark_sensitive_group_targets_from_paths(&args.groups_files) {
+            error!("failed to load group membership files: {}", e);
+            std::process::exit(13);
+        }
+    }
+
+    if let Err(e) = engine.apply_config_policies() {
+        error!("failed to apply config policies: {}", e);
+        std::process::exit(8);
+    }
+
+    let locale_ftl = match &args.locale_file {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(s) => Some(s),
+            Err(e) => {
+                error!("failed to read locale file {}: {}", path.display(), e);
+                std::process::exit(9);
+            }
+        },
+        None => None,
+    };
 
     if !args.quiet {
         // Print banner and summary
         println!("{}", ASCII_TITLE.bold().green());
-        let summary = render_summary_with_top(&engine, args.top_limit);
+        let summary = if args.diagnostics {
+            render_summary_with_diagnostics(&engine, args.top_limit)
+        } else {
+            let localized = render_summary_localized(
+                &engine,
+                args.top_limit,
+                &args.locale,
+                locale_ftl.as_deref(),
+            );
+            match localized {
+                Ok(s) => s,
+                Err(e) => {
+                    error!("failed to render summary: {}", e);
+                    std::process::exit(10);
+                }
+            }
+        };
         println!("{}", summary);
     }
 
@@ -212,6 +338,33 @@ fn main() {
             error!("failed to write {}: {}", txt.display(), e);
             std::process::exit(6);
         }
+        for fmt in &args.formats {
+            match fmt {
+                ExportFormat::Jsonl => {
+                    let path = outdir.join(format!("tattletale_credentials_{}.jsonl", ts));
+                    if let Err(e) = to_jsonl(&engine, &path) {
+                        error!("failed to write {}: {}", path.display(), e);
+                        std::process::exit(14);
+                    }
+                }
+                #[cfg(feature = "bson-export")]
+                ExportFormat::Bson => {
+                    let path = outdir.join(format!("tattletale_credentials_{}.bson", ts));
+                    if let Err(e) = to_bson(&engine, &path) {
+                        error!("failed to write {}: {}", path.display(), e);
+                        std::process::exit(15);
+                    }
+                }
+                #[cfg(feature = "xlsx-export")]
+                ExportFormat::Xlsx => {
+                    let path = outdir.join(format!("tattletale_report_{}.xlsx", ts));
+                    if let Err(e) = to_xlsx(&engine, &path) {
+                        error!("failed to write {}: {}", path.display(), e);
+                        std::process::exit(16);
+                    }
+                }
+            }
+        }
     }
 
     if args.log_parse_stats {
@@ -221,6 +374,9 @@ fn main() {
                 stats.dit_malformed,
                 stats.pot_malformed
             );
+            for (path, codec) in &stats.codecs {
+                log::info!("parse stats: {} decoded as {}", path, codec);
+            }
         } else {
             log::info!("parse stats: (not collected for this run)");
         }
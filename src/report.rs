@@ -2,11 +2,20 @@
 //!
 //! Produces a colored summary including overall statistics, high-value target
 //! status, and shared hash groupings (both target-inclusive and overall).
+//! Section headers, statistic labels, and target/crack callouts are resolved
+//! through a Fluent bundle (see `locale`), defaulting to the built-in English
+//! bundle; `render_summary_localized` accepts another locale at runtime.
+use anyhow::Result;
 use colored::*;
 
 use crate::{
     engine::Engine,
-    stats::{calculate_statistics, domains_breakdown, top_reused_passwords},
+    fingerprint::{compute_dataset_root, root_to_hex},
+    locale::Localizer,
+    stats::{
+        SharedGroup, Statistics, calculate_statistics, domains_breakdown, shared_hash_groups,
+        top_reused_hashes, top_reused_passwords,
+    },
 };
 
 fn visible_len(s: &str) -> usize {
@@ -46,26 +55,58 @@ pub fn render_summary(engine: &Engine) -> String {
 }
 
 pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
+    render_summary_localized(engine, top_n, "en", None)
+        .expect("built-in English locale bundle is always valid")
+}
+
+/// Like `render_summary_with_top`, but resolves section headers, statistic
+/// labels, and target/crack callouts through a Fluent bundle for `locale`
+/// (a BCP-47 tag, e.g. `"es"`) instead of the built-in English text.
+/// `custom_ftl` is that locale's `.ftl` source (e.g. read from disk by the
+/// caller); pass `None` to render in the built-in English bundle. Message
+/// IDs missing from `custom_ftl` fall back to English, so a partial
+/// translation still renders something. Account counts are passed through
+/// Fluent's plural rules (`accounts-count`) instead of a hardcoded
+/// "Accounts" suffix.
+pub fn render_summary_localized(
+    engine: &Engine,
+    top_n: usize,
+    locale: &str,
+    custom_ftl: Option<&str>,
+) -> Result<String> {
+    let stats = calculate_statistics(&engine.credentials);
+    let groups = shared_hash_groups(&engine.credentials);
+    render_summary_localized_with(engine, top_n, locale, custom_ftl, &stats, &groups)
+}
+
+/// Like `render_summary_localized`, but takes already-computed `stats`/
+/// `groups` instead of recomputing them, so callers that need those results
+/// for another purpose (e.g. `render_summary_with_diagnostics` timing them)
+/// don't pay for the full-dataset passes twice.
+fn render_summary_localized_with(
+    engine: &Engine,
+    top_n: usize,
+    locale: &str,
+    custom_ftl: Option<&str>,
+    stats: &Statistics,
+    groups: &[SharedGroup<'_>],
+) -> Result<String> {
+    let loc = Localizer::new(locale, custom_ftl)?;
     let mut out = String::new();
-    out.push_str(&format!(
-        "{}\n",
-        "TattleTale: Domain Secrets (NTDS) Analysis Results"
-            .bold()
-            .cyan()
-    ));
+    out.push_str(&format!("{}\n", loc.format("title", None).bold().cyan()));
 
     // Password statistics
-    let stats = calculate_statistics(&engine.credentials);
-    let mut stats_lines: Vec<String> = Vec::new();
-    stats_lines.push(format!("Total creds: {}", engine.credentials.len()));
-    stats_lines.push(format!("All User Hashes: {}", stats.user.all_count));
-    stats_lines.push(format!("All Machine Hashes: {}", stats.machine.all_count));
-    stats_lines.push(format!("Removable Empty Hashes: {}", stats.null.all_count));
-    stats_lines.push(format!("No-Domain Hashes: {}", stats.no_domain.all_count));
-    stats_lines.push(format!(
-        "Remaining User Hashes: {}",
-        stats.valid_domain_user.all_count
-    ));
+    let mut stats_lines: Vec<String> = vec![
+        loc.format_count("stat-total-creds", engine.credentials.len()),
+        loc.format_count("stat-all-user-hashes", stats.user.all_count),
+        loc.format_count("stat-all-machine-hashes", stats.machine.all_count),
+        loc.format_count("stat-removable-empty-hashes", stats.null.all_count),
+        loc.format_count("stat-no-domain-hashes", stats.no_domain.all_count),
+        loc.format_count(
+            "stat-remaining-user-hashes",
+            stats.valid_domain_user.all_count,
+        ),
+    ];
     for (label, s) in [
         ("Valid Domain User", &stats.valid_domain_user),
         ("No Domain", &stats.no_domain),
@@ -73,18 +114,38 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
         ("NT", &stats.nt),
     ] {
         stats_lines.push(label.bold().blue().to_string());
-        stats_lines.push(format!("  All: {}", s.all_count));
-        stats_lines.push(format!("  Cracked: {}", s.cracked_count));
-        stats_lines.push(format!("  Cracked Percentage: {}", s.cracked_percentage));
-        stats_lines.push(format!("  Unique: {}", s.unique_count));
-        stats_lines.push(format!("  Cracked Unique: {}", s.unique_cracked_count));
+        stats_lines.push(format!("  {}: {}", loc.format("label-all", None), s.all_count));
+        stats_lines.push(format!(
+            "  {}: {}",
+            loc.format("label-cracked", None),
+            s.cracked_count
+        ));
         stats_lines.push(format!(
-            "  Cracked Unique Percentage: {}",
+            "  {}: {}",
+            loc.format("label-cracked-percentage", None),
+            s.cracked_percentage
+        ));
+        stats_lines.push(format!(
+            "  {}: {}",
+            loc.format("label-unique", None),
+            s.unique_count
+        ));
+        stats_lines.push(format!(
+            "  {}: {}",
+            loc.format("label-cracked-unique", None),
+            s.unique_cracked_count
+        ));
+        stats_lines.push(format!(
+            "  {}: {}",
+            loc.format("label-cracked-unique-percentage", None),
             s.unique_cracked_percentage
         ));
     }
     out.push_str(&section_header(
-        &"Password Hash Statistics".bold().yellow().to_string(),
+        &loc.format("section-password-hash-stats", None)
+            .bold()
+            .yellow()
+            .to_string(),
     ));
     for line in stats_lines {
         out.push_str(&line);
@@ -106,13 +167,15 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
     uncracked_users.sort_by(|a, b| a.down_level_logon_name.cmp(&b.down_level_logon_name));
     let mut hvt_lines: Vec<String> = Vec::new();
     if cracked_users.is_empty() && uncracked_users.is_empty() {
-        hvt_lines.push("(No target files provided or no targets matched)".to_string());
+        hvt_lines.push(loc.format("no-targets", None));
     } else {
-        hvt_lines.push(format!(
-            "Cracked {}/{}",
-            cracked_users.len(),
-            cracked_users.len() + uncracked_users.len()
-        ));
+        let mut args = fluent_bundle::FluentArgs::new();
+        args.set("cracked", cracked_users.len() as i64);
+        args.set(
+            "total",
+            (cracked_users.len() + uncracked_users.len()) as i64,
+        );
+        hvt_lines.push(loc.format("hvt-cracked-ratio", Some(&args)));
         for c in cracked_users {
             hvt_lines.push(format!("  {}: {}", c.down_level_logon_name, c.cleartext));
         }
@@ -120,12 +183,15 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
             hvt_lines.push(format!(
                 "  {}: {}",
                 c.down_level_logon_name,
-                "(Not cracked)".dimmed()
+                loc.format("not-cracked-hvt", None).dimmed()
             ));
         }
     }
     out.push_str(&section_header(
-        &"High-Value Targets".bold().cyan().to_string(),
+        &loc.format("section-high-value-targets", None)
+            .bold()
+            .cyan()
+            .to_string(),
     ));
     for line in hvt_lines {
         out.push_str(&line);
@@ -133,56 +199,45 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
     }
 
     // Shared Password Hashes (with at least 1 target)
-    let title_with_target = "Shared Password Hashes (with at least 1 high-value target)"
+    let title_with_target = loc
+        .format("section-shared-with-target", None)
         .bold()
         .cyan()
         .to_string();
-    let mut shared: std::collections::HashMap<&str, Vec<&crate::credential::Credential>> =
-        std::collections::HashMap::new();
-    for c in &engine.credentials {
-        if !c.is_hash_null && !c.hashtext.is_empty() {
-            shared.entry(c.hashtext.as_str()).or_default().push(c);
-        }
-    }
     let mut any_with_target = false;
     let mut with_target_lines: Vec<String> = Vec::new();
-    for (hash, creds) in shared.iter() {
-        if creds.len() > 1 && creds.iter().any(|c| c.is_target) {
-            any_with_target = true;
-            let cracked_cleartext = creds
-                .iter()
-                .find(|c| c.is_cracked)
-                .map(|c| c.cleartext.as_str());
-            with_target_lines.push(match cracked_cleartext {
-                Some(p) => format!("{} - {} ({} Accounts)", hash, p.red(), creds.len()),
-                None => format!(
-                    "{} - {} ({} Accounts)",
-                    hash,
-                    "(Not Cracked)".dimmed(),
-                    creds.len()
-                ),
-            });
-            let mut list = creds.clone();
-            list.sort_by(|a, b| a.down_level_logon_name.cmp(&b.down_level_logon_name));
-            for c in list {
-                if c.is_target {
-                    with_target_lines.push(format!(
-                        "  {}: {}",
-                        c.down_level_logon_name,
-                        "(Target)".red()
-                    ));
-                } else {
-                    with_target_lines.push(format!(
-                        "  {}: {}",
-                        c.down_level_logon_name,
-                        "(Not a target)".dimmed()
-                    ));
-                }
+    for group in groups.iter().filter(|g| g.any_target()) {
+        any_with_target = true;
+        let accounts = loc.format_count("accounts-count", group.creds.len());
+        with_target_lines.push(match group.cracked_cleartext() {
+            Some(p) => format!("{} - {} ({})", group.hashtext, p.red(), accounts),
+            None => format!(
+                "{} - {} ({})",
+                group.hashtext,
+                loc.format("not-cracked", None).dimmed(),
+                accounts
+            ),
+        });
+        let mut list = group.creds.clone();
+        list.sort_by(|a, b| a.down_level_logon_name.cmp(&b.down_level_logon_name));
+        for c in list {
+            if c.is_target {
+                with_target_lines.push(format!(
+                    "  {}: {}",
+                    c.down_level_logon_name,
+                    loc.format("label-target", None).red()
+                ));
+            } else {
+                with_target_lines.push(format!(
+                    "  {}: {}",
+                    c.down_level_logon_name,
+                    loc.format("label-not-a-target", None).dimmed()
+                ));
             }
         }
     }
     if !any_with_target {
-        with_target_lines.push("(No shared hashes with targets)".to_string());
+        with_target_lines.push(loc.format("no-shared-with-targets", None));
     }
     out.push_str(&section_header(&title_with_target));
     for line in with_target_lines {
@@ -190,49 +245,45 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
         out.push('\n');
     }
 
-    // Shared Password Hashes
-    let mut any_shared = false;
+    // Shared Password Hashes (overall)
     let mut shared_lines: Vec<String> = Vec::new();
-    for (hash, creds) in shared.iter() {
-        if creds.len() > 1 {
-            any_shared = true;
-            let cracked_cleartext = creds
-                .iter()
-                .find(|c| c.is_cracked)
-                .map(|c| c.cleartext.as_str());
-            shared_lines.push(match cracked_cleartext {
-                Some(p) => format!("{} - {} ({} Accounts)", hash, p.red(), creds.len()),
-                None => format!(
-                    "{} - {} ({} Accounts)",
-                    hash,
-                    "(Not Cracked)".dimmed(),
-                    creds.len()
-                ),
-            });
-            let mut list = creds.clone();
-            list.sort_by(|a, b| a.down_level_logon_name.cmp(&b.down_level_logon_name));
-            for c in list {
-                if c.is_target {
-                    shared_lines.push(format!(
-                        "  {}: {}",
-                        c.down_level_logon_name,
-                        "(Target)".red()
-                    ));
-                } else {
-                    shared_lines.push(format!(
-                        "  {}: {}",
-                        c.down_level_logon_name,
-                        "(Not a target)".dimmed()
-                    ));
-                }
+    for group in groups.iter() {
+        let accounts = loc.format_count("accounts-count", group.creds.len());
+        shared_lines.push(match group.cracked_cleartext() {
+            Some(p) => format!("{} - {} ({})", group.hashtext, p.red(), accounts),
+            None => format!(
+                "{} - {} ({})",
+                group.hashtext,
+                loc.format("not-cracked", None).dimmed(),
+                accounts
+            ),
+        });
+        let mut list = group.creds.clone();
+        list.sort_by(|a, b| a.down_level_logon_name.cmp(&b.down_level_logon_name));
+        for c in list {
+            if c.is_target {
+                shared_lines.push(format!(
+                    "  {}: {}",
+                    c.down_level_logon_name,
+                    loc.format("label-target", None).red()
+                ));
+            } else {
+                shared_lines.push(format!(
+                    "  {}: {}",
+                    c.down_level_logon_name,
+                    loc.format("label-not-a-target", None).dimmed()
+                ));
             }
         }
     }
-    if !any_shared {
-        shared_lines.push("(No shared hashes)".to_string());
+    if groups.is_empty() {
+        shared_lines.push(loc.format("no-shared-hashes", None));
     }
     out.push_str(&section_header(
-        &"Shared Password Hashes".bold().cyan().to_string(),
+        &loc.format("section-shared-overall", None)
+            .bold()
+            .cyan()
+            .to_string(),
     ));
     for line in shared_lines {
         out.push_str(&line);
@@ -246,23 +297,43 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
         .collect::<Vec<(String, crate::stats::BasicStats)>>();
     by_domain.sort_by(|a, b| a.0.cmp(&b.0));
     if by_domain.is_empty() {
-        domain_lines.push("(No domains)".to_string());
+        domain_lines.push(loc.format("no-domains", None));
     } else {
         for (dom, s) in by_domain {
             domain_lines.push(format!("{}", dom.bold().green()));
-            domain_lines.push(format!("  All: {}", s.all_count));
-            domain_lines.push(format!("  Cracked: {}", s.cracked_count));
-            domain_lines.push(format!("  Cracked Percentage: {}", s.cracked_percentage));
-            domain_lines.push(format!("  Unique: {}", s.unique_count));
-            domain_lines.push(format!("  Cracked Unique: {}", s.unique_cracked_count));
+            domain_lines.push(format!("  {}: {}", loc.format("label-all", None), s.all_count));
+            domain_lines.push(format!(
+                "  {}: {}",
+                loc.format("label-cracked", None),
+                s.cracked_count
+            ));
+            domain_lines.push(format!(
+                "  {}: {}",
+                loc.format("label-cracked-percentage", None),
+                s.cracked_percentage
+            ));
+            domain_lines.push(format!(
+                "  {}: {}",
+                loc.format("label-unique", None),
+                s.unique_count
+            ));
+            domain_lines.push(format!(
+                "  {}: {}",
+                loc.format("label-cracked-unique", None),
+                s.unique_cracked_count
+            ));
             domain_lines.push(format!(
-                "  Cracked Unique Percentage: {}",
+                "  {}: {}",
+                loc.format("label-cracked-unique-percentage", None),
                 s.unique_cracked_percentage
             ));
         }
     }
     out.push_str(&section_header(
-        &"Domain Breakdown".bold().cyan().to_string(),
+        &loc.format("section-domain-breakdown", None)
+            .bold()
+            .cyan()
+            .to_string(),
     ));
     for line in domain_lines {
         out.push_str(&line);
@@ -273,20 +344,125 @@ pub fn render_summary_with_top(engine: &Engine, top_n: usize) -> String {
     let mut top_lines: Vec<String> = Vec::new();
     let top = top_reused_passwords(&engine.credentials, top_n);
     if top.is_empty() {
-        top_lines.push("(No cracked passwords)".to_string());
+        top_lines.push(loc.format("no-cracked-passwords", None));
     } else {
         for (pw, count) in top {
             top_lines.push(format!("  {}: {}", pw, count));
         }
     }
     out.push_str(&section_header(
-        &"Top Reused Passwords".bold().magenta().to_string(),
+        &loc.format("section-top-reused-passwords", None)
+            .bold()
+            .magenta()
+            .to_string(),
     ));
     for line in top_lines {
         out.push_str(&line);
         out.push('\n');
     }
 
+    // Password Reuse Clusters
+    let mut reuse_lines: Vec<String> = Vec::new();
+    let reuse_clusters = top_reused_hashes(&engine.credentials, top_n);
+    if reuse_clusters.is_empty() {
+        reuse_lines.push(loc.format("no-password-reuse", None));
+    } else {
+        for cluster in &reuse_clusters {
+            let target_flag = if cluster.has_target {
+                format!(" {}", loc.format("includes-high-value-target", None).red())
+            } else {
+                String::new()
+            };
+            let accounts = loc.format_count("accounts-count", cluster.accounts.len());
+            reuse_lines.push(match &cluster.cracked_cleartext {
+                Some(p) => format!(
+                    "{} - {} ({}){}",
+                    cluster.hashtext,
+                    p.red(),
+                    accounts,
+                    target_flag
+                ),
+                None => format!(
+                    "{} - {} ({}){}",
+                    cluster.hashtext,
+                    loc.format("not-cracked", None).dimmed(),
+                    accounts,
+                    target_flag
+                ),
+            });
+            for account in &cluster.accounts {
+                reuse_lines.push(format!("  {}", account));
+            }
+        }
+    }
+    out.push_str(&section_header(
+        &loc.format("section-password-reuse-clusters", None)
+            .bold()
+            .magenta()
+            .to_string(),
+    ));
+    for line in reuse_lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    // Dataset Fingerprint
+    let root = compute_dataset_root(&engine.credentials);
+    out.push_str(&section_header(
+        &loc.format("section-dataset-fingerprint", None)
+            .bold()
+            .magenta()
+            .to_string(),
+    ));
+    out.push_str(&format!("{}\n", root_to_hex(&root)));
+
+    Ok(out)
+}
+
+/// Like `render_summary_with_top`, but appends an opt-in "Diagnostics"
+/// section with the load-phase timings/counters from `Engine::diagnostics`
+/// (if the engine was loaded via a `_with_diagnostics` variant) plus timings
+/// for the `calculate_statistics`, shared-hash-grouping, and render phases
+/// run here.
+pub fn render_summary_with_diagnostics(engine: &Engine, top_n: usize) -> String {
+    let stats_start = std::time::Instant::now();
+    let stats = calculate_statistics(&engine.credentials);
+    let calculate_statistics_micros = stats_start.elapsed().as_micros() as u64;
+
+    let grouping_start = std::time::Instant::now();
+    let groups = shared_hash_groups(&engine.credentials);
+    let shared_hash_grouping_micros = grouping_start.elapsed().as_micros() as u64;
+
+    let render_start = std::time::Instant::now();
+    let mut out = render_summary_localized_with(engine, top_n, "en", None, &stats, &groups)
+        .expect("built-in English locale bundle is always valid");
+    let render_summary_micros = render_start.elapsed().as_micros() as u64;
+
+    out.push_str(&section_header(
+        &"Diagnostics".bold().yellow().to_string(),
+    ));
+    match engine.diagnostics {
+        Some(d) => {
+            out.push_str(&format!("DIT parse: {} us\n", d.dit_parse_micros));
+            out.push_str(&format!("Crack tagging: {} us\n", d.crack_tag_micros));
+            out.push_str(&format!("Total lines parsed: {}\n", d.total_lines_parsed));
+            out.push_str(&format!(
+                "Malformed lines skipped: {}\n",
+                d.malformed_lines_skipped
+            ));
+        }
+        None => out.push_str("(load-phase diagnostics not collected for this run)\n"),
+    }
+    out.push_str(&format!(
+        "calculate_statistics: {} us\n",
+        calculate_statistics_micros
+    ));
+    out.push_str(&format!(
+        "shared_hash_groups: {} us\n",
+        shared_hash_grouping_micros
+    ));
+    out.push_str(&format!("render_summary: {} us\n", render_summary_micros));
+
     out
 }
 
@@ -317,4 +493,28 @@ mod tests {
         assert!(s.contains("pw: 2"));
         assert!(!s.contains("other: 1"));
     }
+
+    #[test]
+    fn password_reuse_clusters_section_lists_shared_hash_accounts() {
+        let mut e = Engine::new();
+        let dit = "DOM\\A:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c\nDOM\\B:2:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        let pot = "8846f7eaee8fb117ad06bdd830b7586c:password";
+        e.load_from_strings(&[dit], &[pot], &[]);
+        let s = render_summary(&e);
+        assert!(s.contains("Password Reuse Clusters"));
+        assert!(s.contains("DOM\\A"));
+        assert!(s.contains("DOM\\B"));
+    }
+
+    #[test]
+    fn render_summary_localized_uses_custom_ftl_section_titles() {
+        let mut e = Engine::new();
+        let dit = "DOM\\A:1:aad3b435b51404eeaad3b435b51404ee:8846f7eaee8fb117ad06bdd830b7586c";
+        e.load_from_strings(&[dit], &[], &[]);
+        let custom = "section-high-value-targets = Objetivos de Alto Valor\n";
+        let s = render_summary_localized(&e, 10, "es", Some(custom)).unwrap();
+        assert!(s.contains("Objetivos de Alto Valor"));
+        // not defined in the custom bundle, falls back to English
+        assert!(s.contains("Password Hash Statistics"));
+    }
 }
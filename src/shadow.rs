@@ -0,0 +1,120 @@
+//! Parser for Unix `/etc/passwd` + `/etc/shadow` account entries.
+//!
+//! Supported line formats:
+//! - passwd: `name:x:uid:gid:gecos:home:shell`
+//! - shadow: `name:$id$salt$hash:lastchg:min:max:warn:inactive:expire:`
+//!
+//! Mirrors umanux's model of joining the two files by username: passwd
+//! supplies identity, shadow supplies the crypt hash. Accounts present in
+//! passwd with no matching (or malformed) shadow entry are skipped; `!`,
+//! `!!`, `*`, and empty hash fields mark a locked/disabled account rather
+//! than a malformed one.
+use std::collections::HashMap;
+
+use crate::credential::Credential;
+
+#[derive(Debug, thiserror::Error)]
+/// Errors returned while parsing passwd/shadow lines.
+pub enum ShadowError {
+    #[error("malformed passwd line: {0}")]
+    MalformedPasswdLine(String),
+    #[error("malformed shadow line: {0}")]
+    MalformedShadowLine(String),
+}
+
+/// Parse a single `/etc/passwd` line, returning just the username field
+/// (remaining account metadata isn't modeled by `Credential`).
+pub fn parse_passwd_line(line: &str) -> Result<String, ShadowError> {
+    let name = line
+        .split(':')
+        .next()
+        .ok_or_else(|| ShadowError::MalformedPasswdLine(line.to_string()))?
+        .trim();
+    if name.is_empty() {
+        return Err(ShadowError::MalformedPasswdLine(line.to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Parse a single `/etc/shadow` line into `(username, crypt_hash)`.
+pub fn parse_shadow_line(line: &str) -> Result<(String, String), ShadowError> {
+    let mut parts = line.split(':');
+    let name = parts
+        .next()
+        .ok_or_else(|| ShadowError::MalformedShadowLine(line.to_string()))?
+        .trim();
+    let hash = parts
+        .next()
+        .ok_or_else(|| ShadowError::MalformedShadowLine(line.to_string()))?
+        .trim();
+    if name.is_empty() {
+        return Err(ShadowError::MalformedShadowLine(line.to_string()));
+    }
+    Ok((name.to_string(), hash.to_string()))
+}
+
+/// Parse `/etc/passwd` and `/etc/shadow` contents, joining shadow entries
+/// into passwd accounts by username via `Credential::fill_from_shadow`.
+pub fn parse_shadow_contents(passwd: &str, shadow: &str) -> Vec<Credential> {
+    let mut hashes: HashMap<String, String> = HashMap::new();
+    for line in shadow.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Ok((name, hash)) = parse_shadow_line(line) {
+            hashes.insert(name, hash);
+        }
+    }
+
+    let mut creds = Vec::new();
+    for line in passwd.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Ok(name) = parse_passwd_line(line) else {
+            continue;
+        };
+        let Some(hash) = hashes.get(&name) else {
+            continue;
+        };
+        let mut c = Credential::new();
+        c.fill_from_shadow(&name, hash);
+        creds.push(c);
+    }
+    creds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_passwd_and_shadow_by_username() {
+        let passwd = "alice:x:1000:1000:Alice:/home/alice:/bin/bash\n";
+        let shadow = "alice:$6$abc$def123:19000:0:99999:7:::\n";
+        let creds = parse_shadow_contents(passwd, shadow);
+        assert_eq!(creds.len(), 1);
+        assert_eq!(creds[0].sam_account_name, "alice");
+        assert_eq!(creds[0].hashtext, "$6$abc$def123");
+        assert!(!creds[0].is_hash_null);
+    }
+
+    #[test]
+    fn locked_accounts_are_flagged_null_without_hashtext() {
+        let passwd = "root:x:0:0:root:/root:/bin/bash\n";
+        let shadow = "root:!:19000:0:99999:7:::\n";
+        let creds = parse_shadow_contents(passwd, shadow);
+        assert_eq!(creds.len(), 1);
+        assert!(creds[0].is_hash_null);
+        assert!(creds[0].hashtext.is_empty());
+    }
+
+    #[test]
+    fn accounts_missing_a_shadow_entry_are_skipped() {
+        let passwd = "bob:x:1001:1001:Bob:/home/bob:/bin/bash\n";
+        let creds = parse_shadow_contents(passwd, "");
+        assert!(creds.is_empty());
+    }
+}
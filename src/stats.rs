@@ -5,7 +5,9 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-use crate::credential::Credential;
+use rayon::prelude::*;
+
+use crate::credential::{Credential, HashType};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct BasicStats {
@@ -60,58 +62,131 @@ pub struct Statistics {
     pub no_domain: BasicStats,
 }
 
-pub fn calculate_statistics(all: &[Credential]) -> Statistics {
-    let mut user = Vec::new();
-    let mut machine = Vec::new();
-    let mut valid_domain_user = Vec::new();
-    let mut valid_machine = Vec::new();
-    let mut lm = Vec::new();
-    let mut nt = Vec::new();
-    let mut both = Vec::new();
-    let mut null = Vec::new();
-    let mut no_domain = Vec::new();
+/// Running totals for a single statistics bucket (e.g. "LM hashes"). Unlike
+/// `BasicStats`, the unique counts are tracked as sets so chunk-local partials
+/// can be merged by `extend`-ing rather than by summing (hash/password sets
+/// from different chunks can overlap, so their sizes are not additive).
+#[derive(Debug, Default, Clone)]
+struct CategoryPartial {
+    all_count: usize,
+    cracked_count: usize,
+    hashes: HashSet<String>,
+    cleartexts: HashSet<String>,
+}
 
-    for c in all {
-        if c.is_user_account {
-            user.push(c.clone());
-            if !c.is_hash_null && !c.domain.is_empty() {
-                valid_domain_user.push(c.clone());
-            }
+impl CategoryPartial {
+    fn push(&mut self, c: &Credential) {
+        self.all_count += 1;
+        self.hashes.insert(c.hashtext.clone());
+        if c.is_cracked {
+            self.cracked_count += 1;
+            self.cleartexts.insert(c.cleartext.clone());
         }
-        if c.is_machine_account {
-            machine.push(c.clone());
-            if !c.is_hash_null {
-                valid_machine.push(c.clone());
-            }
+    }
+
+    fn merge(mut self, other: CategoryPartial) -> Self {
+        self.all_count += other.all_count;
+        self.cracked_count += other.cracked_count;
+        self.hashes.extend(other.hashes);
+        self.cleartexts.extend(other.cleartexts);
+        self
+    }
+
+    /// Derive the reported percentages and unique counts from the fully
+    /// merged sets. Must only be called once all chunk partials are reduced.
+    fn finish(self) -> BasicStats {
+        BasicStats {
+            all_count: self.all_count,
+            cracked_count: self.cracked_count,
+            cracked_percentage: pct(self.cracked_count, self.all_count),
+            unique_count: self.hashes.len(),
+            unique_cracked_count: self.cleartexts.len(),
+            unique_cracked_percentage: pct(self.cleartexts.len(), self.hashes.len()),
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct StatsPartial {
+    user: CategoryPartial,
+    machine: CategoryPartial,
+    valid_domain_user: CategoryPartial,
+    valid_machine: CategoryPartial,
+    lm: CategoryPartial,
+    nt: CategoryPartial,
+    both: CategoryPartial,
+    null: CategoryPartial,
+    no_domain: CategoryPartial,
+}
+
+impl StatsPartial {
+    fn merge(self, other: StatsPartial) -> Self {
+        StatsPartial {
+            user: self.user.merge(other.user),
+            machine: self.machine.merge(other.machine),
+            valid_domain_user: self.valid_domain_user.merge(other.valid_domain_user),
+            valid_machine: self.valid_machine.merge(other.valid_machine),
+            lm: self.lm.merge(other.lm),
+            nt: self.nt.merge(other.nt),
+            both: self.both.merge(other.both),
+            null: self.null.merge(other.null),
+            no_domain: self.no_domain.merge(other.no_domain),
         }
-        if !c.is_hash_null {
-            if c.is_hash_type_lm {
-                lm.push(c.clone());
+    }
+}
+
+/// Single-pass, parallel computation of [`Statistics`] over the whole
+/// credential set. Each chunk folds into a local `StatsPartial` (counts plus
+/// hash/password sets) with no per-category `Vec<Credential>` clones, and
+/// chunks are merged pairwise with `reduce`; percentages and unique counts
+/// are only derived from the final, fully-merged sets.
+pub fn calculate_statistics(all: &[Credential]) -> Statistics {
+    let partial = all
+        .par_iter()
+        .fold(StatsPartial::default, |mut acc, c| {
+            if c.is_user_account {
+                acc.user.push(c);
+                if !c.is_hash_null && !c.domain.is_empty() {
+                    acc.valid_domain_user.push(c);
+                }
             }
-            if c.is_hash_type_nt {
-                nt.push(c.clone());
+            if c.is_machine_account {
+                acc.machine.push(c);
+                if !c.is_hash_null {
+                    acc.valid_machine.push(c);
+                }
             }
-            if c.is_hash_type_both {
-                both.push(c.clone());
+            if !c.is_hash_null {
+                match c.hash_type {
+                    HashType::Lm => acc.lm.push(c),
+                    HashType::Nt => acc.nt.push(c),
+                    HashType::LmAndNt => {
+                        acc.lm.push(c);
+                        acc.nt.push(c);
+                        acc.both.push(c);
+                    }
+                    _ => {}
+                }
+            } else {
+                acc.null.push(c);
             }
-        } else {
-            null.push(c.clone());
-        }
-        if c.domain.is_empty() {
-            no_domain.push(c.clone());
-        }
-    }
+            if c.domain.is_empty() {
+                acc.no_domain.push(c);
+            }
+            acc
+        })
+        .reduce(StatsPartial::default, StatsPartial::merge);
 
     Statistics {
-        user: analyze_creds(&user),
-        machine: analyze_creds(&machine),
-        valid_domain_user: analyze_creds(&valid_domain_user),
-        valid_machine: analyze_creds(&valid_machine),
-        lm: analyze_creds(&lm),
-        nt: analyze_creds(&nt),
-        both: analyze_creds(&both),
-        null: analyze_creds(&null),
-        no_domain: analyze_creds(&no_domain),
+        user: partial.user.finish(),
+        machine: partial.machine.finish(),
+        valid_domain_user: partial.valid_domain_user.finish(),
+        valid_machine: partial.valid_machine.finish(),
+        lm: partial.lm.finish(),
+        nt: partial.nt.finish(),
+        both: partial.both.finish(),
+        null: partial.null.finish(),
+        no_domain: partial.no_domain.finish(),
     }
 }
 
@@ -132,6 +207,73 @@ pub fn domains_breakdown(all: &[Credential]) -> HashMap<String, BasicStats> {
     out
 }
 
+/// Number of hash-prefix bins used by [`shared_hash_groups`]. Chosen to match
+/// the range of a single hex byte so the bin assignment below is a plain
+/// byte-to-bin identity mapping.
+const BIN_COUNT: usize = 256;
+
+/// A set of credentials that all share the same non-null `hashtext`.
+#[derive(Debug, Clone)]
+pub struct SharedGroup<'a> {
+    pub hashtext: &'a str,
+    pub creds: Vec<&'a Credential>,
+}
+
+impl<'a> SharedGroup<'a> {
+    /// The cleartext of the first cracked member, if any.
+    pub fn cracked_cleartext(&self) -> Option<&'a str> {
+        self.creds
+            .iter()
+            .find(|c| c.is_cracked)
+            .map(|c| c.cleartext.as_str())
+    }
+
+    /// Whether any member of the group is flagged as a high-value target.
+    pub fn any_target(&self) -> bool {
+        self.creds.iter().any(|c| c.is_target)
+    }
+}
+
+/// Bin index for a hash string, taken from its first hex byte (two hex
+/// chars). Identical hashes always land in the same bin, so grouping within
+/// a bin and concatenating bins is equivalent to grouping over the whole set.
+fn hash_bin(hashtext: &str) -> usize {
+    hashtext
+        .get(0..2)
+        .and_then(|prefix| u8::from_str_radix(prefix, 16).ok())
+        .map(|b| b as usize)
+        .unwrap_or(0)
+}
+
+/// Find groups of credentials that share an identical (non-null) `hashtext`,
+/// i.e. password reuse. Credentials are first bucketed into `BIN_COUNT` bins
+/// by hash prefix so the O(N) grouping pass within each bin can run
+/// concurrently via `rayon`, instead of building one large `HashMap` over the
+/// entire dataset. Only groups with more than one member are returned.
+pub fn shared_hash_groups(creds: &[Credential]) -> Vec<SharedGroup<'_>> {
+    let mut bins: Vec<Vec<&Credential>> = vec![Vec::new(); BIN_COUNT];
+    for c in creds {
+        if c.is_hash_null || c.hashtext.is_empty() {
+            continue;
+        }
+        bins[hash_bin(&c.hashtext)].push(c);
+    }
+
+    bins.par_iter()
+        .flat_map_iter(|bin| {
+            let mut by_hash: HashMap<&str, Vec<&Credential>> = HashMap::new();
+            for &c in bin {
+                by_hash.entry(c.hashtext.as_str()).or_default().push(c);
+            }
+            by_hash
+                .into_iter()
+                .filter(|(_, members)| members.len() > 1)
+                .map(|(hashtext, creds)| SharedGroup { hashtext, creds })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 /// Return the top-N most reused cracked passwords across all credentials.
 /// Returns a vector of (password, count) sorted descending by count, then
 /// ascending by password to stabilize ordering for tests.
@@ -154,6 +296,96 @@ pub fn top_reused_passwords(all: &[Credential], top_n: usize) -> Vec<(String, us
     items
 }
 
+/// Fast `[u8; 16]`-keyed map for [`password_reuse_clusters`], hashed with
+/// `ahash` instead of the default SipHash so indexing millions of hashes
+/// stays cheap.
+type FastHashIndex = hashbrown::HashMap<[u8; 16], Vec<usize>, ahash::RandomState>;
+
+/// Parse a 32-hex-char NT/LM `hashtext` into its raw 16 bytes. Returns
+/// `None` for anything else (crypt/LDAP hashes, empty, or malformed hex),
+/// since those aren't comparable as fixed-width NT/LM hashes.
+fn hex_to_bytes16(hashtext: &str) -> Option<[u8; 16]> {
+    let bytes = hashtext.as_bytes();
+    if bytes.len() != 32 || !bytes.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    let mut out = [0u8; 16];
+    for (i, slot) in out.iter_mut().enumerate() {
+        let hi = (bytes[i * 2] as char).to_digit(16)?;
+        let lo = (bytes[i * 2 + 1] as char).to_digit(16)?;
+        *slot = (hi * 16 + lo) as u8;
+    }
+    Some(out)
+}
+
+/// A group of credentials that share the same NT/LM hash, i.e. password
+/// reuse. `has_target` flags the high-signal case of an admin password
+/// reused on lower-value accounts. See [`password_reuse_clusters`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReuseCluster {
+    pub hashtext: String,
+    pub accounts: Vec<String>,
+    pub cracked_cleartext: Option<String>,
+    pub has_target: bool,
+}
+
+/// Group credentials with a 32-hex-char NT/LM `hashtext` by that hash to
+/// surface password reuse across the domain. The index is built over a
+/// `hashbrown::HashMap<[u8; 16], Vec<usize>>` keyed by the hash's raw bytes
+/// and hashed with `ahash`, so indexing stays cheap on multi-million-row
+/// datasets. Only hashes shared by more than one account are returned,
+/// sorted by descending membership count (ties broken by hex hash
+/// ascending, to stabilize ordering for tests and reports).
+pub fn password_reuse_clusters(all: &[Credential]) -> Vec<ReuseCluster> {
+    let mut index: FastHashIndex = FastHashIndex::default();
+    for (i, c) in all.iter().enumerate() {
+        if c.is_hash_null {
+            continue;
+        }
+        if let Some(key) = hex_to_bytes16(&c.hashtext) {
+            index.entry(key).or_default().push(i);
+        }
+    }
+
+    let mut clusters: Vec<ReuseCluster> = index
+        .into_values()
+        .filter(|idxs| idxs.len() > 1)
+        .map(|idxs| {
+            let members: Vec<&Credential> = idxs.iter().map(|&i| &all[i]).collect();
+            let mut accounts: Vec<String> = members
+                .iter()
+                .map(|c| c.down_level_logon_name.clone())
+                .collect();
+            accounts.sort();
+            ReuseCluster {
+                hashtext: members[0].hashtext.clone(),
+                cracked_cleartext: members
+                    .iter()
+                    .find(|c| c.is_cracked)
+                    .map(|c| c.cleartext.clone()),
+                has_target: members.iter().any(|c| c.is_target),
+                accounts,
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        (std::cmp::Reverse(a.accounts.len()), &a.hashtext)
+            .cmp(&(std::cmp::Reverse(b.accounts.len()), &b.hashtext))
+    });
+    clusters
+}
+
+/// The top-`top_n` password-reuse clusters by member count, for
+/// `report::render_summary`.
+pub fn top_reused_hashes(all: &[Credential], top_n: usize) -> Vec<ReuseCluster> {
+    let mut clusters = password_reuse_clusters(all);
+    if clusters.len() > top_n {
+        clusters.truncate(top_n);
+    }
+    clusters
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +427,70 @@ mod tests {
         assert_eq!(top[1].0, "word");
         assert_eq!(top[1].1, 1);
     }
+
+    #[test]
+    fn password_reuse_clusters_groups_by_nt_hash_and_flags_targets() {
+        let shared_hash = "8846f7eaee8fb117ad06bdd830b7586c";
+        let mut admin = Credential::new();
+        admin.fill_from_dit("DOM\\Admin", Credential::NULL_HASH_LM, shared_hash);
+        admin.crack("password");
+        admin.is_target = true;
+        let mut user = Credential::new();
+        user.fill_from_dit("DOM\\User", Credential::NULL_HASH_LM, shared_hash);
+        let mut lonely = Credential::new();
+        lonely.fill_from_dit(
+            "DOM\\Lonely",
+            Credential::NULL_HASH_LM,
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        );
+
+        let clusters = password_reuse_clusters(&[admin, user, lonely]);
+        assert_eq!(clusters.len(), 1);
+        let cluster = &clusters[0];
+        assert_eq!(cluster.hashtext, shared_hash);
+        assert_eq!(cluster.accounts, vec!["DOM\\Admin", "DOM\\User"]);
+        assert_eq!(cluster.cracked_cleartext.as_deref(), Some("password"));
+        assert!(cluster.has_target);
+    }
+
+    #[test]
+    fn password_reuse_clusters_ignores_malformed_non_hex_hashtext_instead_of_panicking() {
+        // A 30-char, 32-byte multi-byte-UTF-8 hashtext would previously slice
+        // mid-character inside `hex_to_bytes16` and panic.
+        let mut weird = Credential::new();
+        let bogus_hash = format!("\u{20ac}{}", "a".repeat(29));
+        assert_eq!(bogus_hash.len(), 32);
+        weird.fill_from_dit("DOM\\Weird", Credential::NULL_HASH_LM, &bogus_hash);
+        let mut other = Credential::new();
+        other.fill_from_dit(
+            "DOM\\Other",
+            Credential::NULL_HASH_LM,
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        );
+
+        let clusters = password_reuse_clusters(&[weird, other]);
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn top_reused_hashes_respects_limit_and_sorts_by_count_desc() {
+        let hash_a = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let hash_b = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let mut creds = Vec::new();
+        for i in 0..3 {
+            let mut c = Credential::new();
+            c.fill_from_dit(&format!("DOM\\A{i}"), Credential::NULL_HASH_LM, hash_a);
+            creds.push(c);
+        }
+        for i in 0..2 {
+            let mut c = Credential::new();
+            c.fill_from_dit(&format!("DOM\\B{i}"), Credential::NULL_HASH_LM, hash_b);
+            creds.push(c);
+        }
+
+        let top = top_reused_hashes(&creds, 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].hashtext, hash_a);
+        assert_eq!(top[0].accounts.len(), 3);
+    }
 }